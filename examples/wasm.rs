@@ -0,0 +1,57 @@
+//! A game loop in the browser, driven by `requestAnimationFrame`.
+//!
+//! Build with `cargo build --example wasm --features wasm --target wasm32-unknown-unknown`, then
+//! generate the JS bindings with `wasm-bindgen --target web` and load the output from a page.
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use gameloop::{FrameAction, GameLoop, WasmClock};
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen::JsCast;
+
+    fn request_frame(callback: &Closure<dyn FnMut()>) {
+        web_sys::window()
+            .expect("no window")
+            .request_animation_frame(callback.as_ref().unchecked_ref())
+            .expect("requestAnimationFrame failed");
+    }
+
+    // Instant::now() would panic here, so use the browser's clock
+    let game_loop = GameLoop::with_clock(20, 5, WasmClock::new()).unwrap();
+    let mut ticks = 0;
+
+    // the callback reschedules itself every frame, so it needs a handle to itself
+    let callback = Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>));
+    let next_frame = callback.clone();
+
+    *callback.borrow_mut() = Some(Closure::new(move || {
+        for action in game_loop.actions() {
+            match action {
+                FrameAction::Tick { .. } => {
+                    ticks += 1;
+                    web_sys::console::log_1(&format!("tick {}", ticks).into());
+                }
+                FrameAction::Ticks { count, .. } => {
+                    ticks += count;
+                    web_sys::console::log_1(&format!("tick {}", ticks).into());
+                }
+                FrameAction::Render { interpolation } => {
+                    let _ = interpolation; // draw to a canvas here
+                }
+                _ => {}
+            }
+        }
+
+        request_frame(next_frame.borrow().as_ref().unwrap());
+    }));
+
+    request_frame(callback.borrow().as_ref().unwrap());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    eprintln!("this example only runs on wasm32-unknown-unknown");
+}
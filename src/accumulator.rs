@@ -0,0 +1,231 @@
+use core::convert::TryFrom;
+use core::time::Duration;
+
+use crate::atomic::AtomicDuration;
+#[cfg(feature = "std")]
+use crate::clock::SystemClock;
+use crate::clock::{Clock, DefaultClock};
+use crate::gameloop::{tick_interval, valid_tps, FrameAction, GameLoopError};
+
+/// An alternative to `GameLoop` that uses the
+/// [accumulator](https://gafferongames.com/post/fix_your_timestep/) model: the real time of each
+/// frame is added to an accumulator, which is drained in fixed steps of one tick each.
+///
+/// `GameLoop` schedules each tick at an absolute time, so after a long stall, such as the OS
+/// suspending the process, it has a backlog of ticks that is only worked through
+/// `max_frameskip` at a time. Here, the time absorbed in a single frame is capped at
+/// `max_frameskip` ticks worth, so the excess is dropped and the game carries on as if the stall
+/// never happened.
+///
+/// As with `GameLoop`, the first tick is due immediately.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let game_loop = AccumulatorLoop::with_clock(20, 5, &clock).unwrap();
+/// game_loop.actions().for_each(drop);
+///
+/// // the machine sleeps for a minute
+/// clock.advance(Duration::from_secs(60));
+/// let ticks = game_loop
+///     .actions()
+///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+///     .count();
+/// assert_eq!(ticks, 5);
+///
+/// // there is no backlog to work through
+/// let actions: Vec<_> = game_loop.actions().collect();
+/// assert!(matches!(actions[..], [FrameAction::Render { .. }]));
+/// ```
+pub struct AccumulatorLoop<C: Clock = DefaultClock> {
+    /// The source of time
+    clock: C,
+
+    /// Time between each game tick
+    skip_ticks: Duration,
+
+    /// Maximum number of consecutive ticks before a render is mandatory
+    max_frameskip: usize,
+
+    /// Maximum time absorbed into the accumulator in a single frame
+    max_frame_time: Duration,
+
+    /// Clock time at the start of the last frame
+    last_frame: AtomicDuration,
+
+    /// Time not yet consumed by ticks
+    accumulator: AtomicDuration,
+}
+
+#[cfg(feature = "std")]
+impl AccumulatorLoop {
+    /// Create a new accumulator loop that runs in real time. The arguments have the same meaning
+    /// as for `GameLoop::new`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// let game_loop = AccumulatorLoop::new(20, 5);
+    /// assert!(game_loop.is_ok());
+    ///
+    /// // tps and max_frameskip must be >= 1
+    /// assert!(AccumulatorLoop::new(0, 1).is_err());
+    /// assert!(AccumulatorLoop::new(1, 0).is_err());
+    /// ```
+    pub fn new(tps: usize, max_frameskip: usize) -> Result<Self, GameLoopError> {
+        Self::with_clock(tps, max_frameskip, SystemClock::new())
+    }
+}
+
+impl<C: Clock> AccumulatorLoop<C> {
+    /// Create a new accumulator loop driven by the given clock. See `new` for the meaning of the
+    /// other arguments.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = AccumulatorLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// // the first tick is due immediately, and two more have accumulated
+    /// clock.advance(Duration::from_millis(120));
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 3);
+    /// ```
+    pub fn with_clock(tps: usize, max_frameskip: usize, clock: C) -> Result<Self, GameLoopError> {
+        if !valid_tps(tps) {
+            return Err(GameLoopError::BadTps(tps));
+        }
+
+        if max_frameskip == 0 {
+            return Err(GameLoopError::BadFrameSkip(max_frameskip));
+        }
+
+        let skip_ticks = tick_interval(tps);
+        let max_frame_time = u32::try_from(max_frameskip)
+            .ok()
+            .and_then(|frames| skip_ticks.checked_mul(frames))
+            .unwrap_or(Duration::MAX);
+        let now = clock.now();
+
+        Ok(Self {
+            clock,
+            skip_ticks,
+            max_frameskip,
+            max_frame_time,
+            last_frame: AtomicDuration::new(now),
+            accumulator: AtomicDuration::new(skip_ticks),
+        })
+    }
+
+    /// Should be called once per iteration of your core game loop. The time since the last call
+    /// is absorbed into the accumulator, up to `max_frameskip` ticks worth.
+    ///
+    /// The interpolation of each `FrameAction::Render` is the fraction of a tick left in the
+    /// accumulator, clamped to `[0.0, 1.0]`. More than a tick is left when ticks are put off to a
+    /// later frame, such as when an iterator is dropped before its ticks are taken, or when
+    /// another thread adds time to the accumulator.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = AccumulatorLoop::with_clock(20, 2, &clock).unwrap();
+    ///
+    /// // the ticks of this frame are left in the accumulator
+    /// clock.advance(Duration::from_millis(100));
+    /// drop(game_loop.actions());
+    ///
+    /// // so after two ticks, more than a tick is still left
+    /// clock.advance(Duration::from_millis(100));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Render { interpolation }
+    ///     ] if interpolation == 1.0
+    /// ));
+    /// ```
+    pub fn actions(&self) -> AccumulatorActions<'_, C> {
+        let now = self.clock.now();
+        let frame_time = now
+            .saturating_sub(self.last_frame.swap(now))
+            .min(self.max_frame_time);
+        self.accumulator.add(frame_time);
+
+        AccumulatorActions {
+            game_loop: self,
+            loops: 0,
+            rendered: false,
+        }
+    }
+
+    /// Time between each game tick.
+    pub fn skip_ticks(&self) -> Duration {
+        self.skip_ticks
+    }
+
+    /// Maximum number of consecutive ticks before a render is mandatory.
+    pub fn max_frameskip(&self) -> usize {
+        self.max_frameskip
+    }
+}
+
+/// Iterator of `FrameAction`s, returned by `AccumulatorLoop::actions`.
+///
+/// At most `max_frameskip` ticks and one render are produced, which is reflected in the upper
+/// bound of `size_hint`.
+pub struct AccumulatorActions<'a, C: Clock = DefaultClock> {
+    game_loop: &'a AccumulatorLoop<C>,
+
+    loops: usize,
+    rendered: bool,
+}
+
+impl<'a, C: Clock> Iterator for AccumulatorActions<'a, C> {
+    type Item = FrameAction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let skip_ticks = self.game_loop.skip_ticks;
+        let accumulator = loop {
+            let accumulator = self.game_loop.accumulator.get();
+            if accumulator < skip_ticks || self.loops >= self.game_loop.max_frameskip {
+                break accumulator;
+            }
+
+            // if another thread drained the accumulator first, try again with what is left
+            if self
+                .game_loop
+                .accumulator
+                .compare_exchange(accumulator, accumulator - skip_ticks)
+            {
+                self.loops += 1;
+                return Some(FrameAction::Tick { delta: skip_ticks });
+            }
+        };
+
+        if !self.rendered {
+            self.rendered = true;
+
+            let interpolation = (accumulator.as_secs_f64() / skip_ticks.as_secs_f64()).min(1.0);
+            return Some(FrameAction::Render { interpolation });
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let ticks = self.game_loop.max_frameskip - self.loops;
+        let render = if self.rendered { 0 } else { 1 };
+        (0, ticks.checked_add(render))
+    }
+}
@@ -0,0 +1,119 @@
+use core::convert::TryFrom;
+use core::time::Duration;
+use portable_atomic::{AtomicU64, Ordering};
+
+/// A `Duration` that can be shared between threads, stored as whole nanoseconds. Durations
+/// beyond `u64::MAX` nanoseconds (over 584 years) saturate.
+#[derive(Debug, Default)]
+pub(crate) struct AtomicDuration(AtomicU64);
+
+/// An `Option<Duration>` that can be shared between threads, using `u64::MAX` nanoseconds to
+/// represent `None`.
+#[derive(Debug)]
+pub(crate) struct AtomicOptionDuration(AtomicU64);
+
+/// An `f64` that can be shared between threads, stored as its bits.
+#[derive(Debug)]
+pub(crate) struct AtomicF64(AtomicU64);
+
+fn to_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+impl AtomicDuration {
+    pub(crate) fn new(duration: Duration) -> Self {
+        Self(AtomicU64::new(to_nanos(duration)))
+    }
+
+    pub(crate) fn get(&self) -> Duration {
+        Duration::from_nanos(self.0.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set(&self, duration: Duration) {
+        self.0.store(to_nanos(duration), Ordering::Relaxed);
+    }
+
+    /// Replaces the duration, returning the previous value.
+    pub(crate) fn swap(&self, duration: Duration) -> Duration {
+        Duration::from_nanos(self.0.swap(to_nanos(duration), Ordering::Relaxed))
+    }
+
+    /// Adds to the duration in a single step, so concurrent additions are never lost. Saturates
+    /// at `u64::MAX` nanoseconds.
+    pub(crate) fn add(&self, duration: Duration) {
+        let nanos = to_nanos(duration);
+        let _ = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                Some(current.saturating_add(nanos))
+            });
+    }
+
+    /// Subtracts from the duration in a single step, saturating at zero.
+    pub(crate) fn saturating_sub(&self, duration: Duration) {
+        let nanos = to_nanos(duration);
+        let _ = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                Some(current.saturating_sub(nanos))
+            });
+    }
+
+    /// Replaces the duration with `new` only if it is still `current`, returning whether it was.
+    pub(crate) fn compare_exchange(&self, current: Duration, new: Duration) -> bool {
+        self.0
+            .compare_exchange(
+                to_nanos(current),
+                to_nanos(new),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+}
+
+impl AtomicOptionDuration {
+    const NONE: u64 = u64::MAX;
+
+    pub(crate) fn new(duration: Option<Duration>) -> Self {
+        Self(AtomicU64::new(duration.map_or(Self::NONE, to_nanos)))
+    }
+
+    pub(crate) fn get(&self) -> Option<Duration> {
+        match self.0.load(Ordering::Relaxed) {
+            Self::NONE => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+
+    pub(crate) fn set(&self, duration: Option<Duration>) {
+        self.0
+            .store(duration.map_or(Self::NONE, to_nanos), Ordering::Relaxed);
+    }
+
+    /// Replaces the duration, returning the previous value.
+    #[cfg(feature = "std")]
+    pub(crate) fn swap(&self, duration: Option<Duration>) -> Option<Duration> {
+        match self
+            .0
+            .swap(duration.map_or(Self::NONE, to_nanos), Ordering::Relaxed)
+        {
+            Self::NONE => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+}
+
+impl AtomicF64 {
+    pub(crate) fn new(value: f64) -> Self {
+        Self(AtomicU64::new(value.to_bits()))
+    }
+
+    pub(crate) fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
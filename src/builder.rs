@@ -0,0 +1,471 @@
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use crate::clock::SystemClock;
+use crate::clock::{Clock, DefaultClock};
+use crate::config::{GameLoopConfig, TimingState};
+use crate::gameloop::{GameLoop, GameLoopError, InterpolationMode};
+
+/// Configures and creates a `GameLoop`.
+///
+/// Unset options take sensible defaults: 30 ticks per second, a fixed max frame skip of 5,
+/// interpolation enabled but not clamped, interpolating between ticks, rendering while paused, not
+/// headless, not lockstep, no late tick, no init action, no frame start, no tick coalescing, no
+/// render rate cap, no catch-up limit, no frame time budget, a frame time average over 60
+/// renders, no action recording, and the real time `SystemClock`.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// let game_loop = GameLoopBuilder::new()
+///     .tps(60)
+///     .max_frameskip(10)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameLoopBuilder<C: Clock = DefaultClock> {
+    pub(crate) tick_rate: TickRate,
+    pub(crate) max_frameskip: usize,
+    pub(crate) adaptive_frameskip: Option<(usize, usize)>,
+    pub(crate) interpolation: bool,
+    pub(crate) clamp_interpolation: bool,
+    pub(crate) interpolation_mode: InterpolationMode,
+    pub(crate) render_while_paused: bool,
+    pub(crate) headless: bool,
+    pub(crate) lockstep: bool,
+    pub(crate) late_tick: bool,
+    pub(crate) init: bool,
+    pub(crate) frame_start: bool,
+    pub(crate) coalesce_ticks: bool,
+    pub(crate) max_fps: Option<usize>,
+    pub(crate) max_catchup: Option<Duration>,
+    pub(crate) max_frame_time: Option<Duration>,
+    #[cfg(feature = "std")]
+    pub(crate) frame_time_window: usize,
+    #[cfg(feature = "std")]
+    pub(crate) record_actions: bool,
+    pub(crate) timing: Option<TimingState>,
+    pub(crate) clock: C,
+}
+
+#[cfg(feature = "std")]
+impl GameLoopBuilder {
+    /// Creates a builder with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for GameLoopBuilder {
+    fn default() -> Self {
+        Self::with_clock(SystemClock::new())
+    }
+}
+
+impl<C: Clock> GameLoopBuilder<C> {
+    /// Creates a builder with the default options, driven by the given clock. Without the `std`
+    /// feature, this is the only way to create a builder.
+    pub fn with_clock(clock: C) -> Self {
+        Self {
+            tick_rate: TickRate::PerSecond(30),
+            max_frameskip: 5,
+            adaptive_frameskip: None,
+            interpolation: true,
+            clamp_interpolation: false,
+            interpolation_mode: InterpolationMode::Interpolate,
+            render_while_paused: true,
+            headless: false,
+            lockstep: false,
+            late_tick: false,
+            init: false,
+            frame_start: false,
+            coalesce_ticks: false,
+            max_fps: None,
+            max_catchup: None,
+            max_frame_time: None,
+            #[cfg(feature = "std")]
+            frame_time_window: 60,
+            #[cfg(feature = "std")]
+            record_actions: false,
+            timing: None,
+            clock,
+        }
+    }
+
+    /// Game ticks per second, must be >= 1 and at most one per nanosecond.
+    pub fn tps(mut self, tps: usize) -> Self {
+        self.tick_rate = TickRate::PerSecond(tps);
+        self
+    }
+
+    /// Time between each game tick, must be non-zero. This is an alternative to `tps`, and
+    /// whichever is set last takes effect.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let tick = Duration::from_micros(41_667);
+    /// let game_loop = GameLoopBuilder::new().tick_duration(tick).build().unwrap();
+    ///
+    /// // each tick carries the configured duration
+    /// assert_eq!(
+    ///     game_loop.actions().next(),
+    ///     Some(FrameAction::Tick { delta: tick })
+    /// );
+    /// ```
+    pub fn tick_duration(mut self, tick: Duration) -> Self {
+        self.tick_rate = TickRate::Interval(tick);
+        self
+    }
+
+    /// Maximum number of consecutive ticks before a render is mandatory, must be >= 1. See
+    /// `GameLoop::new` for details, and `GameLoop::new_unbounded` for the risks of `usize::MAX`,
+    /// which means no limit.
+    pub fn max_frameskip(mut self, max_frameskip: usize) -> Self {
+        self.max_frameskip = max_frameskip;
+        self
+    }
+
+    /// Lets the max frame skip adapt to the load, within `min..=max`, instead of being fixed. This
+    /// replaces `max_frameskip`, and requires `1 <= min <= max`.
+    ///
+    /// The max frame skip starts at `min`. Each iteration of `GameLoop::actions` that falls behind
+    /// raises it by one, so sustained catch-up is allowed more ticks per frame before the game
+    /// slows down. Each iteration that keeps up lowers it by one, so a brief stall causes less of
+    /// a stutter. The current value is given by `GameLoop::max_frameskip`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(100)
+    ///     .adaptive_frameskip(2, 10)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(game_loop.max_frameskip(), 2);
+    ///
+    /// // frames take far longer than a tick
+    /// for _ in 0..5 {
+    ///     clock.advance(Duration::from_millis(100));
+    ///     game_loop.actions().for_each(drop);
+    /// }
+    /// assert_eq!(game_loop.max_frameskip(), 7);
+    ///
+    /// // the load eases off, and the backlog is worked through
+    /// while game_loop.max_frameskip() > 2 {
+    ///     clock.advance(Duration::from_millis(1));
+    ///     game_loop.actions().for_each(drop);
+    /// }
+    /// assert!(!game_loop.is_behind());
+    ///
+    /// assert!(matches!(
+    ///     GameLoopBuilder::new().adaptive_frameskip(5, 2).build(),
+    ///     Err(GameLoopError::BadFrameSkipRange(5, 2))
+    /// ));
+    /// ```
+    pub fn adaptive_frameskip(mut self, min: usize, max: usize) -> Self {
+        self.adaptive_frameskip = Some((min, max));
+        self
+    }
+
+    /// Whether to compute the interpolation of each `FrameAction::Render`. If disabled, it is
+    /// always 1.0, so the game renders exactly the latest tick, e.g. for pixel art with no motion
+    /// between ticks.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .interpolation(false)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// for _ in 0..10 {
+    ///     clock.advance(Duration::from_millis(15));
+    ///     let actions: Vec<_> = game_loop.actions().collect();
+    ///     assert_eq!(
+    ///         actions.last(),
+    ///         Some(&FrameAction::Render { interpolation: 1.0 })
+    ///     );
+    /// }
+    /// assert_eq!(game_loop.current_interpolation(), 1.0);
+    /// ```
+    pub fn interpolation(mut self, interpolation: bool) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Whether to clamp the interpolation of each `FrameAction::Render` to `[0.0, 1.0]`. See
+    /// `GameLoop::new_clamped` for details. This has no effect with
+    /// `InterpolationMode::Extrapolate`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::new()
+    ///     .tps(20)
+    ///     .max_frameskip(2)
+    ///     .clamp_interpolation(true)
+    ///     .clock(&clock)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // fall far behind, so every frame hits max_frameskip
+    /// for _ in 0..10 {
+    ///     clock.advance(Duration::from_secs(1));
+    ///     for action in game_loop.actions() {
+    ///         if let FrameAction::Render { interpolation } = action {
+    ///             assert!((0.0..=1.0).contains(&interpolation));
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn clamp_interpolation(mut self, clamp: bool) -> Self {
+        self.clamp_interpolation = clamp;
+        self
+    }
+
+    /// How the factor of each `FrameAction::Render` is meant to be used. See `InterpolationMode`
+    /// for details.
+    pub fn interpolation_mode(mut self, mode: InterpolationMode) -> Self {
+        self.interpolation_mode = mode;
+        self
+    }
+
+    /// Whether `FrameAction::Render` is still emitted while the loop is paused. If disabled,
+    /// `GameLoop::actions` yields nothing at all while paused, other than any
+    /// `FrameAction::FrameStart`.
+    pub fn render_while_paused(mut self, render: bool) -> Self {
+        self.render_while_paused = render;
+        self
+    }
+
+    /// Whether to never render, e.g. for a dedicated server. If enabled, `GameLoop::actions` only
+    /// ever yields `FrameAction::Tick`. See `GameLoop::new_headless`.
+    pub fn headless(mut self, headless: bool) -> Self {
+        self.headless = headless;
+        self
+    }
+
+    /// Whether each iteration emits exactly one tick and a render, regardless of the clock. See
+    /// `GameLoop::new_lockstep` for details.
+    pub fn lockstep(mut self, lockstep: bool) -> Self {
+        self.lockstep = lockstep;
+        self
+    }
+
+    /// Whether to emit a `FrameAction::LateTick` after the last tick of each iteration, for a
+    /// separate late update phase before rendering. See `FrameAction::LateTick` for details.
+    pub fn late_tick(mut self, late_tick: bool) -> Self {
+        self.late_tick = late_tick;
+        self
+    }
+
+    /// Whether to emit a `FrameAction::Init` once, at the start of the first iteration. See
+    /// `FrameAction::Init` for details.
+    pub fn init(mut self, init: bool) -> Self {
+        self.init = init;
+        self
+    }
+
+    /// Whether to emit a `FrameAction::FrameStart` at the start of every iteration. See
+    /// `FrameAction::FrameStart` for details.
+    pub fn frame_start(mut self, frame_start: bool) -> Self {
+        self.frame_start = frame_start;
+        self
+    }
+
+    /// Whether to emit the ticks of each iteration as a single `FrameAction::Ticks`, rather than
+    /// a `FrameAction::Tick` each. See `FrameAction::Ticks` for details.
+    pub fn coalesce_ticks(mut self, coalesce: bool) -> Self {
+        self.coalesce_ticks = coalesce;
+        self
+    }
+
+    /// Maximum number of renders per second, must be >= 1. Unlimited by default.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::new()
+    ///     .max_fps(10)
+    ///     .clock(&clock)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // iterate every millisecond for a second
+    /// let mut renders = 0;
+    /// for _ in 0..1000 {
+    ///     renders += game_loop
+    ///         .actions()
+    ///         .filter(|action| matches!(action, FrameAction::Render { .. }))
+    ///         .count();
+    ///     clock.advance(Duration::from_millis(1));
+    /// }
+    ///
+    /// assert_eq!(renders, 10);
+    /// ```
+    pub fn max_fps(mut self, max_fps: usize) -> Self {
+        self.max_fps = Some(max_fps);
+        self
+    }
+
+    /// Maximum backlog of overdue game time to catch up on. Unlimited by default.
+    ///
+    /// After a long stall, e.g. when the machine sleeps, the loop would otherwise emit
+    /// `max_frameskip` ticks every iteration until it catches up, which can take a very long
+    /// time. Instead, once the backlog exceeds this, the overdue ticks are skipped and a
+    /// `FrameAction::TimeJumped` is emitted, and the loop carries on from the current time.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::new()
+    ///     .tps(20)
+    ///     .max_frameskip(5)
+    ///     .max_catchup(Duration::from_secs(1))
+    ///     .clock(&clock)
+    ///     .build()
+    ///     .unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // the machine sleeps for a minute
+    /// clock.advance(Duration::from_secs(60));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert_eq!(
+    ///     actions[0],
+    ///     FrameAction::TimeJumped {
+    ///         skipped: Duration::from_millis(59_950)
+    ///     }
+    /// );
+    /// assert!(matches!(
+    ///     actions[1..],
+    ///     [FrameAction::Tick { .. }, FrameAction::Render { .. }]
+    /// ));
+    /// assert!(!game_loop.is_behind());
+    ///
+    /// // there is no backlog to work through
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(actions[..], [FrameAction::Render { .. }]));
+    /// ```
+    pub fn max_catchup(mut self, max_catchup: Duration) -> Self {
+        self.max_catchup = Some(max_catchup);
+        self
+    }
+
+    /// Maximum real time to spend ticking in each iteration of `GameLoop::actions`. Unlimited by
+    /// default. See `GameLoop::new_with_budget` for details.
+    pub fn max_frame_time(mut self, max_frame_time: Duration) -> Self {
+        self.max_frame_time = Some(max_frame_time);
+        self
+    }
+
+    /// Number of renders that `GameLoop::average_frame_time` averages over, must be >= 1. 60 by
+    /// default. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn frame_time_window(mut self, renders: usize) -> Self {
+        self.frame_time_window = renders;
+        self
+    }
+
+    /// Whether to record every action produced, to be taken with `GameLoop::take_log`. Nothing is
+    /// allocated unless enabled. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn record_actions(mut self, record: bool) -> Self {
+        self.record_actions = record;
+        self
+    }
+
+    /// The source of time to drive the loop with.
+    pub fn clock<D: Clock>(self, clock: D) -> GameLoopBuilder<D> {
+        GameLoopBuilder {
+            tick_rate: self.tick_rate,
+            max_frameskip: self.max_frameskip,
+            adaptive_frameskip: self.adaptive_frameskip,
+            interpolation: self.interpolation,
+            clamp_interpolation: self.clamp_interpolation,
+            interpolation_mode: self.interpolation_mode,
+            render_while_paused: self.render_while_paused,
+            headless: self.headless,
+            lockstep: self.lockstep,
+            late_tick: self.late_tick,
+            init: self.init,
+            frame_start: self.frame_start,
+            coalesce_ticks: self.coalesce_ticks,
+            max_fps: self.max_fps,
+            max_catchup: self.max_catchup,
+            max_frame_time: self.max_frame_time,
+            #[cfg(feature = "std")]
+            frame_time_window: self.frame_time_window,
+            #[cfg(feature = "std")]
+            record_actions: self.record_actions,
+            timing: self.timing,
+            clock,
+        }
+    }
+
+    /// Restores the tick rate, max frame skip, render rate cap and timing state from a snapshot
+    /// taken with `GameLoop::snapshot`. See `GameLoopConfig` for details.
+    pub fn config(mut self, config: GameLoopConfig) -> Self {
+        self.tick_rate = TickRate::Restored {
+            tps: config.tps,
+            interval: config.tick_duration,
+        };
+        self.max_frameskip = config.max_frameskip;
+        self.max_fps = config.max_fps;
+        self.timing = Some(TimingState {
+            game_time: config.game_time,
+            next_tick: config.next_tick,
+            total_ticks: config.total_ticks,
+        });
+        self
+    }
+
+    /// Validates the options and creates the game loop, failing in the same way as
+    /// `GameLoop::new`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// assert!(GameLoopBuilder::default().build().is_ok());
+    ///
+    /// // tps and max_frameskip must be >= 1
+    /// assert!(matches!(
+    ///     GameLoopBuilder::new().tps(0).build(),
+    ///     Err(GameLoopError::BadTps(0))
+    /// ));
+    /// assert!(matches!(
+    ///     GameLoopBuilder::new().max_frameskip(0).build(),
+    ///     Err(GameLoopError::BadFrameSkip(0))
+    /// ));
+    /// ```
+    pub fn build(self) -> Result<GameLoop<C>, GameLoopError> {
+        GameLoop::from_builder(self)
+    }
+}
+
+/// How the tick rate was specified.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TickRate {
+    PerSecond(usize),
+    Interval(Duration),
+    /// Both, as taken from a snapshot of a loop.
+    Restored {
+        tps: usize,
+        interval: Duration,
+    },
+}
@@ -0,0 +1,306 @@
+use core::time::Duration;
+#[cfg(feature = "std")]
+use portable_atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::atomic::AtomicDuration;
+
+/// A source of time for a `GameLoop`.
+///
+/// The default is `SystemClock`, which reads the real time. Tests can substitute a
+/// `ManualClock` to control exactly how much time passes between frames, or a `ScriptedClock` to
+/// replay a fixed sequence of frame times.
+///
+/// # Example
+/// A custom clock that replays the frame times of a recorded session:
+/// ```
+/// # use ::gameloop::*;
+/// # use std::cell::Cell;
+/// # use std::time::Duration;
+/// struct ReplayClock {
+///     frame_times: Vec<Duration>,
+///     frame: Cell<usize>,
+/// }
+///
+/// impl Clock for ReplayClock {
+///     fn now(&self) -> Duration {
+///         self.frame_times[self.frame.get()]
+///     }
+/// }
+///
+/// let clock = ReplayClock {
+///     frame_times: vec![Duration::from_millis(0), Duration::from_millis(120)],
+///     frame: Cell::new(0),
+/// };
+/// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+///
+/// clock.frame.set(1);
+/// let actions: Vec<_> = game_loop.actions().collect();
+/// assert!(matches!(
+///     actions[..],
+///     [
+///         FrameAction::Tick { .. },
+///         FrameAction::Tick { .. },
+///         FrameAction::Tick { .. },
+///         FrameAction::Render { .. }
+///     ]
+/// ));
+/// ```
+pub trait Clock {
+    /// Time elapsed since some fixed point, e.g. when the clock was created. This should never
+    /// decrease between calls. If it does, the loop doesn't panic, but emits no ticks until the
+    /// clock has caught up again.
+    fn now(&self) -> Duration;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// Wall clock time, backed by `Instant`. Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    epoch: Instant,
+}
+
+/// Stands in as the default clock type parameter when the `std` feature is disabled, and so
+/// `SystemClock` is unavailable. This can never be created, so a clock must always be given
+/// explicitly, e.g. with `GameLoop::with_clock`.
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Clone, Copy)]
+pub enum NoClock {}
+
+/// The clock used when none is specified.
+#[cfg(feature = "std")]
+pub type DefaultClock = SystemClock;
+
+/// The clock used when none is specified.
+#[cfg(not(feature = "std"))]
+pub type DefaultClock = NoClock;
+
+/// Browser time from `performance.now()`, for `wasm32-unknown-unknown` where `SystemClock`
+/// panics. Requires the `wasm` feature.
+#[cfg(feature = "wasm")]
+#[derive(Debug, Clone)]
+pub struct WasmClock {
+    performance: web_sys::Performance,
+    epoch: f64,
+}
+
+/// A clock that only advances when told to, for deterministic testing.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// assert_eq!(clock.now(), Duration::from_secs(0));
+///
+/// clock.advance(Duration::from_millis(250));
+/// assert_eq!(clock.now(), Duration::from_millis(250));
+/// ```
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: AtomicDuration,
+}
+
+/// A clock that replays a scripted sequence of frame times, for deterministic testing of irregular
+/// frames such as a stall. It starts at zero, and each call to `next_frame` moves it forward by
+/// the next delta in the script. Requires the `std` feature.
+///
+/// # Example
+/// A spike in the frame time is caught up on over the following frames, `max_frameskip` ticks at
+/// a time:
+/// ```
+/// # use ::gameloop::*;
+/// # use std::time::Duration;
+/// let clock = ScriptedClock::new(vec![
+///     Duration::from_millis(5),
+///     Duration::from_millis(200),
+///     Duration::from_millis(5),
+///     Duration::from_millis(5),
+/// ]);
+/// let game_loop = GameLoop::with_clock(20, 2, &clock).unwrap();
+/// game_loop.actions().for_each(drop);
+///
+/// let mut ticks = vec![];
+/// while clock.next_frame().is_some() {
+///     let actions: Vec<_> = game_loop.actions().collect();
+///     assert!(matches!(actions.last(), Some(FrameAction::Render { .. })));
+///     ticks.push(actions.len() - 1);
+/// }
+///
+/// // the ticks at 50ms, 100ms, 150ms and 200ms are all due after the spike
+/// assert_eq!(ticks, vec![0, 2, 2, 0]);
+/// assert_eq!(clock.now(), Duration::from_millis(215));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ScriptedClock {
+    deltas: Vec<Duration>,
+    next: AtomicUsize,
+    now: AtomicDuration,
+}
+
+#[cfg(feature = "std")]
+impl SystemClock {
+    /// Creates a clock whose epoch is now.
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl WasmClock {
+    /// Creates a clock whose epoch is now.
+    ///
+    /// # Panics
+    /// Panics if there is no global `window` with a `performance` object, e.g. in a web worker.
+    pub fn new() -> Self {
+        let performance = web_sys::window()
+            .and_then(|window| window.performance())
+            .expect("performance.now() is unavailable");
+        let epoch = performance.now();
+        Self { performance, epoch }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl Default for WasmClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl Clock for WasmClock {
+    fn now(&self) -> Duration {
+        // milliseconds, which should never decrease but guard against it anyway
+        let elapsed = (self.performance.now() - self.epoch).max(0.0);
+        Duration::from_secs_f64(elapsed / 1000.0)
+    }
+}
+
+impl ManualClock {
+    /// Creates a clock starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the clock forward by the given amount.
+    pub fn advance(&self, by: Duration) {
+        self.now.add(by);
+    }
+
+    /// Moves the clock back by the given amount, stopping at zero, to test a clock that goes
+    /// backwards.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .max_frameskip(100)
+    ///     .max_fps(60)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// clock.advance(Duration::from_secs(1));
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // time goes backwards, so there is nothing to tick but still a render
+    /// clock.rewind(Duration::from_millis(500));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert_eq!(actions, vec![FrameAction::Render { interpolation: 0.0 }]);
+    /// assert!(game_loop.time_until_next_tick() > Duration::from_millis(500));
+    ///
+    /// // and the schedule carries on once the clock catches up
+    /// clock.advance(Duration::from_millis(600));
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 2);
+    /// ```
+    pub fn rewind(&self, by: Duration) {
+        self.now.saturating_sub(by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+#[cfg(feature = "std")]
+impl ScriptedClock {
+    /// Creates a clock starting at zero, that replays the given frame deltas in order.
+    pub fn new(deltas: Vec<Duration>) -> Self {
+        Self {
+            deltas,
+            next: AtomicUsize::new(0),
+            now: AtomicDuration::new(Duration::from_secs(0)),
+        }
+    }
+
+    /// Moves the clock forward by the next delta in the script, and returns it. Once the script
+    /// is exhausted, the clock stays put and this returns `None`.
+    pub fn next_frame(&self) -> Option<Duration> {
+        let next = self
+            .next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |next| {
+                if next < self.deltas.len() {
+                    Some(next + 1)
+                } else {
+                    None
+                }
+            })
+            .ok()?;
+
+        let delta = self.deltas[next];
+        self.now.add(delta);
+        Some(delta)
+    }
+
+    /// The number of frame deltas not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.deltas.len() - self.next.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for ScriptedClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Clock for NoClock {
+    fn now(&self) -> Duration {
+        match *self {}
+    }
+}
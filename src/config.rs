@@ -0,0 +1,78 @@
+use core::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The configuration and timing state of a `GameLoop`, taken with `GameLoop::snapshot`, which can
+/// be saved and later restored with `GameLoop::from_config`.
+///
+/// A clock's time is only meaningful to the process that read it, so times are stored relative
+/// to the start of the game instead. A restored loop continues from the same game time and with
+/// the same progress towards the next tick, regardless of when it is restored. The speed and
+/// pause state are not included, so a restored loop always starts running at normal speed.
+///
+/// With the `serde` feature, this implements `Serialize` and `Deserialize`. Deserialized values
+/// are not trusted, and are validated again when the loop is created.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let game_loop = GameLoopBuilder::new()
+///     .tps(60)
+///     .max_frameskip(3)
+///     .max_fps(144)
+///     .build()
+///     .unwrap();
+///
+/// let json = serde_json::to_string(&game_loop.snapshot()).unwrap();
+/// let config: GameLoopConfig = serde_json::from_str(&json).unwrap();
+/// let restored = GameLoop::from_config(config).unwrap();
+/// assert_eq!(restored.tps(), 60);
+/// assert_eq!(restored.max_frameskip(), 3);
+/// assert_eq!(restored.max_fps(), Some(144));
+///
+/// // an invalid config is rejected
+/// let json = json.replace("\"max_frameskip\":3", "\"max_frameskip\":0");
+/// let config: GameLoopConfig = serde_json::from_str(&json).unwrap();
+/// assert!(matches!(
+///     GameLoop::from_config(config),
+///     Err(GameLoopError::BadFrameSkip(0))
+/// ));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameLoopConfig {
+    /// Game ticks per second, as reported by `GameLoop::tps`. This is kept alongside the tick
+    /// duration so that it is restored exactly, rather than rounded from the duration.
+    pub tps: usize,
+
+    /// Time between each game tick.
+    pub tick_duration: Duration,
+
+    /// Maximum number of consecutive ticks before a render is mandatory.
+    pub max_frameskip: usize,
+
+    /// Maximum number of renders per second, if capped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_fps: Option<usize>,
+
+    /// Game time since the game started.
+    pub game_time: Duration,
+
+    /// Game time of the next scheduled tick.
+    pub next_tick: Duration,
+
+    /// Number of ticks emitted since the game started.
+    pub total_ticks: u64,
+}
+
+/// The timing part of a `GameLoopConfig`, to restore when building a loop.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TimingState {
+    pub(crate) game_time: Duration,
+    pub(crate) next_tick: Duration,
+    pub(crate) total_ticks: u64,
+}
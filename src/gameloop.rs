@@ -1,9 +1,22 @@
-use std::cell::Cell;
+use core::fmt::{Debug, Display, Formatter};
+use core::time::Duration;
+use portable_atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{Display, Formatter};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+#[cfg(feature = "std")]
 use std::time::Instant;
 
-use log::debug;
+#[cfg(feature = "std")]
+use crate::stats::FrameTimes;
+
+use crate::accumulator::AccumulatorLoop;
+use crate::atomic::{AtomicDuration, AtomicF64, AtomicOptionDuration};
+use crate::builder::{GameLoopBuilder, TickRate};
+use crate::clock::{Clock, DefaultClock, ManualClock};
+use crate::config::{GameLoopConfig, TimingState};
+use crate::stats::{LoopStats, StatsTracker};
 
 /// Represents the core loop for the duration of the game.
 ///
@@ -20,44 +33,251 @@ use log::debug;
 ///
 ///     for action in game_loop.actions() {
 ///         match action {
-///             FrameAction::Tick => /* simulate 1 game tick */
+///             FrameAction::Tick { .. } => /* simulate 1 game tick */
 /// # {},
 ///             FrameAction::Render { interpolation } => /* render the game state interpolated
 ///                                                         between previous and next tick */
 /// # {},
+///             FrameAction::TimeJumped { .. } => /* the game time jumped forward */
+/// # {},
+///             FrameAction::LateTick => /* after the ticks, if enabled */
+/// # {},
+///             FrameAction::Init => /* before the first tick, if enabled */
+/// # {},
+///             FrameAction::FrameStart => /* before anything else, if enabled */
+/// # {},
+///             FrameAction::Ticks { count, .. } => /* simulate `count` ticks at once, if enabled */
+/// # {},
+///             _ => /* variants added in later versions */
+/// # {},
 ///         }
 ///     }
 ///
 /// # break; // not infinite pls
 /// }
 /// ```
-pub struct GameLoop {
-    /// The game start time
-    start_time: Instant,
+///
+/// # Accumulator model
+///
+/// Ticks are scheduled at absolute game times, so a long stall is caught up on over the following
+/// iterations. For the [accumulator](https://gafferongames.com/post/fix_your_timestep/) model
+/// instead, which drops the time of a stall beyond `max_frameskip` ticks worth, use
+/// `AccumulatorLoop`. It is created with the same `tps` and `max_frameskip`, and its `actions()`
+/// yields the same `FrameAction`s.
+///
+/// # Threads
+///
+/// A `GameLoop` is `Send` and `Sync` if its clock is, so it can be shared behind an `Arc`, e.g.
+/// between an update thread and a render thread. Each tick is only ever emitted once, even if
+/// several threads iterate `actions()` at the same time.
+///
+/// ```
+/// # use ::gameloop::*;
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// let game_loop = Arc::new(GameLoop::new(100, 5).unwrap());
+///
+/// let update_thread = {
+///     let game_loop = Arc::clone(&game_loop);
+///     std::thread::spawn(move || {
+///         while game_loop.total_ticks() < 3 {
+///             game_loop.actions().for_each(drop);
+///             std::thread::sleep(Duration::from_millis(1));
+///         }
+///     })
+/// };
+///
+/// update_thread.join().unwrap();
+/// assert!(game_loop.total_ticks() >= 3);
+/// ```
+///
+/// The state is kept in atomics with relaxed ordering, which compile to plain loads and stores on
+/// common platforms, so single threaded use costs nothing extra. A render thread can poll
+/// `current_interpolation` while the main thread iterates `actions()`:
+/// ```
+/// # use ::gameloop::*;
+/// # use std::sync::atomic::{AtomicBool, Ordering};
+/// let game_loop = GameLoop::new(100, 5).unwrap();
+/// let quit = AtomicBool::new(false);
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| {
+///         while !quit.load(Ordering::Relaxed) {
+///             let interpolation = game_loop.current_interpolation();
+///             assert!(interpolation >= 0.0);
+///         }
+///     });
+///
+///     for _ in 0..1000 {
+///         game_loop.actions().for_each(drop);
+///     }
+///     quit.store(true, Ordering::Relaxed);
+/// });
+/// ```
+pub struct GameLoop<C: Clock = DefaultClock> {
+    /// The source of time
+    clock: C,
 
-    /// Milliseconds between each game tick
-    skip_ticks: usize,
+    /// Clock time at which the game time was last anchored, i.e. when the loop started or its
+    /// speed last changed
+    anchor_time: AtomicDuration,
+
+    /// Game time at `anchor_time`
+    anchor_game_time: AtomicDuration,
+
+    /// Rate at which game time passes relative to the clock
+    speed: AtomicF64,
+
+    /// Time between each game tick
+    skip_ticks: AtomicDuration,
+
+    /// Game ticks per second as configured, kept as `skip_ticks` is rounded to whole nanoseconds
+    tps: AtomicUsize,
 
     /// Maximum number of consecutive ticks before a render is mandatory.
-    max_frameskip: usize,
+    max_frameskip: AtomicUsize,
+
+    /// Range within which `max_frameskip` adapts to the load, if enabled
+    adaptive_frameskip: Option<(usize, usize)>,
+
+    /// Time since the game started for the next scheduled game tick
+    next_game_tick: AtomicDuration,
+
+    /// Number of ticks emitted since the game started
+    total_ticks: AtomicU64,
+
+    /// Whether render interpolation is computed, rather than always 1.0
+    interpolation: bool,
 
-    /// Time in ms for the next scheduled game tick
-    next_game_tick: Cell<usize>,
+    /// Whether render interpolation is clamped to `[0.0, 1.0]`
+    clamp_interpolation: bool,
+
+    /// How the render factor is meant to be used
+    interpolation_mode: InterpolationMode,
+
+    /// Whether the game time is frozen
+    paused: AtomicBool,
+
+    /// Whether `FrameAction::Render` is still emitted while paused
+    render_while_paused: bool,
+
+    /// Whether `FrameAction::Render` is never emitted
+    headless: bool,
+
+    /// Whether each iteration emits exactly one tick and a render, ignoring the clock
+    lockstep: bool,
+
+    /// Whether `FrameAction::LateTick` is emitted after the ticks of each iteration
+    late_tick: bool,
+
+    /// Whether `FrameAction::Init` is emitted at the start of the first iteration
+    init: bool,
+
+    /// Whether `FrameAction::Init` has been emitted
+    initialized: AtomicBool,
+
+    /// Whether `FrameAction::FrameStart` is emitted at the start of each iteration
+    frame_start: bool,
+
+    /// Whether the ticks of each iteration are emitted as a single `FrameAction::Ticks`
+    coalesce_ticks: bool,
+
+    /// Maximum number of renders per second, if capped
+    max_fps: Option<usize>,
+
+    /// Minimum time between each render, if the render rate is capped
+    min_render_interval: Option<Duration>,
+
+    /// Clock time of the last render
+    last_render: AtomicOptionDuration,
+
+    /// Clock time of the last call to `begin_render`, if `end_render` hasn't been called since
+    render_start: AtomicOptionDuration,
+
+    /// Time between the last `begin_render` and `end_render`
+    render_duration: AtomicDuration,
+
+    /// Measured tick and render rates
+    stats: StatsTracker,
+
+    /// Recent intervals between renders
+    #[cfg(feature = "std")]
+    frame_times: FrameTimes,
+
+    /// Every action produced and the clock time of its iteration, if recording
+    #[cfg(feature = "std")]
+    recording: Option<Mutex<Vec<(Duration, FrameAction)>>>,
+
+    /// The iteration in progress through `poll`, if any
+    #[cfg(feature = "std")]
+    polled: Mutex<Option<FrameState>>,
+
+    /// Whether the last iteration hit `max_frameskip` with ticks still due
+    behind: AtomicBool,
+
+    /// Whether the last iteration held back its render under `max_fps`
+    render_throttled: AtomicBool,
+
+    /// Backlog of overdue game time beyond which the schedule jumps forward instead of catching
+    /// up
+    max_catchup: Option<Duration>,
+
+    /// Maximum real time to spend ticking in each iteration
+    max_frame_time: Option<Duration>,
 }
 
-/// Errors possible when initializing `GameLoop`.
+/// Errors possible when configuring a `GameLoop`. Each carries the offending value, which is
+/// included in its `Display` message. These are never caused by another error, so
+/// `Error::source` is always `None`.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # use std::error::Error;
+/// let err = GameLoop::new(0, 5).err().unwrap();
+/// assert_eq!(
+///     err.to_string(),
+///     "ticks per second must be between 1 and 1000000000, got 0"
+/// );
+/// assert!(err.source().is_none());
+///
+/// let err = GameLoop::new(20, 0).err().unwrap();
+/// assert_eq!(err.to_string(), "max frame skip must be >= 1, got 0");
+///
+/// let err = GameLoop::new(20, 5).unwrap().set_speed(-2.0).unwrap_err();
+/// assert_eq!(err.to_string(), "speed must be finite and >= 0, got -2");
+/// ```
 #[derive(Debug)]
 pub enum GameLoopError {
-    BadTps,
-    BadFrameSkip,
+    /// Ticks per second was less than 1, or more than one per nanosecond.
+    BadTps(usize),
+
+    /// The tick duration was zero.
+    BadTickDuration(Duration),
+
+    /// Max frame skip was less than 1.
+    BadFrameSkip(usize),
+
+    /// The minimum and maximum of an adaptive frame skip were not `1 <= min <= max`.
+    BadFrameSkipRange(usize, usize),
+
+    /// Max frames per second was less than 1.
+    BadMaxFps(usize),
+
+    /// Speed was negative or not finite.
+    BadSpeed(f64),
+
+    /// The number of renders to average the frame time over was less than 1.
+    BadFrameTimeWindow(usize),
 }
 
+#[cfg(feature = "std")]
 impl GameLoop {
-    /// Create a new game loop.
+    /// Create a new game loop that runs in real time.
     /// # Arguments
-    /// * `tps`: game ticks per second
+    /// * `tps`: game ticks per second, at most one per nanosecond
     /// * `max_frameskip`: maximum number of consecutive ticks before a render is mandatory. As
-    /// [deWiTTERS](https://dewitters.com/dewitters-gameloop/) explains:
+    ///   [deWiTTERS](https://dewitters.com/dewitters-gameloop/) explains:
     /// > When running on slow hardware, the framerate can drop until the game update loop will
     /// > reach MAX_FRAMESKIP. In practice this means that when our render FPS drops below 5
     /// > (= FRAMES_PER_SECOND / MAX_FRAMESKIP), the actual game will slow down.
@@ -72,30 +292,651 @@ impl GameLoop {
     /// // tps and max_frameskip must be >= 1
     /// assert!(GameLoop::new(0, 1).is_err());
     /// assert!(GameLoop::new(1, 0).is_err());
+    ///
+    /// // ticks are scheduled to the nanosecond
+    /// assert!(GameLoop::new(1_000_000_000, 5).is_ok());
+    /// assert!(GameLoop::new(1_000_000_001, 5).is_err());
     /// ```
     ///
     pub fn new(tps: usize, max_frameskip: usize) -> Result<Self, GameLoopError> {
-        if tps < 1 {
-            return Err(GameLoopError::BadTps);
+        GameLoopBuilder::new()
+            .tps(tps)
+            .max_frameskip(max_frameskip)
+            .build()
+    }
+
+    /// Create a new game loop that runs in real time, like `new`, but with the interpolation of
+    /// each `FrameAction::Render` clamped to `[0.0, 1.0]`.
+    ///
+    /// Without clamping, the interpolation exceeds 1.0 when the game is running behind and
+    /// `max_frameskip` has been reached, which can cause rendered objects to overshoot.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let game_loop = GameLoop::new_clamped(100, 1).unwrap();
+    ///
+    /// // fall far behind so max_frameskip is reached
+    /// std::thread::sleep(Duration::from_millis(100));
+    ///
+    /// for action in game_loop.actions() {
+    ///     if let FrameAction::Render { interpolation } = action {
+    ///         assert!((0.0..=1.0).contains(&interpolation));
+    ///     }
+    /// }
+    /// ```
+    pub fn new_clamped(tps: usize, max_frameskip: usize) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new()
+            .tps(tps)
+            .max_frameskip(max_frameskip)
+            .clamp_interpolation(true)
+            .build()
+    }
+
+    /// Create a new game loop that runs in real time, like `new`, but with no `max_frameskip`, so
+    /// every iteration of `actions()` catches up fully on all of the ticks that are due. This is
+    /// the same as a `max_frameskip` of `usize::MAX`.
+    ///
+    /// The game never slows down, but if ticks take longer to run than the time between them, each
+    /// iteration has more ticks to catch up on than the last, and the game stops rendering
+    /// altogether. This is known as the spiral of death, so only use this if ticks are known to be
+    /// cheap, or bound the cost of catching up with `GameLoopBuilder::max_catchup` or
+    /// `GameLoopBuilder::max_frame_time`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .max_frameskip(usize::MAX)
+    ///     .build()
+    ///     .unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // a long stall is caught up on in one go
+    /// clock.advance(Duration::from_secs(10));
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 200);
+    /// assert!(!game_loop.is_behind());
+    ///
+    /// assert_eq!(GameLoop::new_unbounded(20).unwrap().max_frameskip(), usize::MAX);
+    /// ```
+    pub fn new_unbounded(tps: usize) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new()
+            .tps(tps)
+            .max_frameskip(usize::MAX)
+            .build()
+    }
+
+    /// Create a new game loop that runs in real time, like `new`, but that never catches up on
+    /// missed ticks, as a classic fixed step loop. Each iteration of `actions()` emits at most one
+    /// tick before rendering, and if the game falls more than a tick behind, the missed ticks are
+    /// dropped rather than run in later iterations.
+    ///
+    /// This is the same as a `max_frameskip` of 1 and a `GameLoopBuilder::max_catchup` of one tick,
+    /// so dropped ticks are reported with a `FrameAction::TimeJumped`. The game slows down, rather
+    /// than stutters, when rendering can't keep up with the tick rate.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .max_frameskip(1)
+    ///     .max_catchup(Duration::from_millis(50))
+    ///     .build()
+    ///     .unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // a stall results in a single tick, not a burst
+    /// clock.advance(Duration::from_secs(1));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [
+    ///         FrameAction::TimeJumped { .. },
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Render { .. }
+    ///     ]
+    /// ));
+    ///
+    /// // and no backlog is left over
+    /// clock.advance(Duration::from_millis(10));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(actions[..], [FrameAction::Render { .. }]));
+    ///
+    /// assert_eq!(GameLoop::new_no_skip(20).unwrap().max_frameskip(), 1);
+    /// assert!(GameLoop::new_no_skip(0).is_err());
+    /// ```
+    pub fn new_no_skip(tps: usize) -> Result<Self, GameLoopError> {
+        if !valid_tps(tps) {
+            return Err(GameLoopError::BadTps(tps));
         }
 
-        if max_frameskip < 1 {
-            return Err(GameLoopError::BadFrameSkip);
+        GameLoopBuilder::new()
+            .tps(tps)
+            .max_frameskip(1)
+            .max_catchup(tick_interval(tps))
+            .build()
+    }
+
+    /// Create a new game loop that runs in real time, like `new`, but renders at most `max_fps`
+    /// times per second. Iterations of `actions()` that are too soon after the last render yield
+    /// only the pending ticks, if any.
+    ///
+    /// This avoids rendering thousands of times per second on fast hardware without vsync.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// // 20 ticks per second, 5 max frame skip, at most 60 renders per second
+    /// let game_loop = GameLoop::with_max_fps(20, 5, 60);
+    /// assert!(game_loop.is_ok());
+    ///
+    /// // max_fps must be >= 1
+    /// assert!(GameLoop::with_max_fps(20, 5, 0).is_err());
+    /// ```
+    pub fn with_max_fps(
+        tps: usize,
+        max_frameskip: usize,
+        max_fps: usize,
+    ) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new()
+            .tps(tps)
+            .max_frameskip(max_frameskip)
+            .max_fps(max_fps)
+            .build()
+    }
+
+    /// Create a new game loop that runs in real time, like `new`, but bounds the cost of catching
+    /// up by time rather than by a number of ticks. Each iteration of `actions()` stops emitting
+    /// ticks once `max_frame_time` of real time has passed since it started, though at least one
+    /// tick is always emitted if due. There is no `max_frameskip`.
+    ///
+    /// This is useful when ticks are expensive, so that even a few catch-up ticks would blow the
+    /// frame budget. The budget can also be combined with `max_frameskip` using
+    /// `GameLoopBuilder::max_frame_time`. The clock is read again before each tick to check the
+    /// budget, but the game time of the iteration is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::new()
+    ///     .tps(100)
+    ///     .max_frameskip(usize::MAX)
+    ///     .max_frame_time(Duration::from_millis(10))
+    ///     .clock(&clock)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // fall far behind, then take 4ms of real time to process each tick
+    /// clock.advance(Duration::from_secs(1));
+    /// let mut ticks = 0;
+    /// for action in game_loop.actions() {
+    ///     if let FrameAction::Tick { .. } = action {
+    ///         clock.advance(Duration::from_millis(4));
+    ///         ticks += 1;
+    ///     }
+    /// }
+    ///
+    /// // the budget is spent after 3 ticks
+    /// assert_eq!(ticks, 3);
+    /// assert!(game_loop.is_behind());
+    ///
+    /// assert!(GameLoop::new_with_budget(100, Duration::from_millis(10)).is_ok());
+    /// ```
+    pub fn new_with_budget(tps: usize, max_frame_time: Duration) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new()
+            .tps(tps)
+            .max_frameskip(usize::MAX)
+            .max_frame_time(max_frame_time)
+            .build()
+    }
+
+    /// Create a new game loop that runs in real time, like `new`, but never renders, e.g. for a
+    /// dedicated server. `actions()` only ever yields `FrameAction::Tick`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::new()
+    ///     .tps(20)
+    ///     .headless(true)
+    ///     .clock(&clock)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut ticks = 0;
+    /// for frame_time in [0, 1, 10, 49, 50, 51, 120, 500, 3, 2000].iter().cycle().take(1000) {
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    ///     for action in game_loop.actions() {
+    ///         assert!(matches!(action, FrameAction::Tick { .. }));
+    ///         ticks += 1;
+    ///     }
+    /// }
+    /// assert!(ticks > 0);
+    ///
+    /// assert!(GameLoop::new_headless(20, 5).is_ok());
+    /// ```
+    pub fn new_headless(tps: usize, max_frameskip: usize) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new()
+            .tps(tps)
+            .max_frameskip(max_frameskip)
+            .headless(true)
+            .build()
+    }
+
+    /// Create a new game loop that emits exactly one tick followed by a render on every iteration
+    /// of `actions()`, regardless of the clock, e.g. to capture video frame by frame. The render's
+    /// interpolation is always 1.0, as the game state is exactly at the tick.
+    ///
+    /// Unlike `step_n_ticks`, this is driven by the usual `actions()` loop. The game runs as fast
+    /// as it is iterated, so `max_frameskip`, `max_fps`, `max_catchup` and the speed have no
+    /// effect, and `time_until_next_tick` is always zero. Pausing still stops ticks.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::new()
+    ///     .tps(24)
+    ///     .lockstep(true)
+    ///     .clock(&clock)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // however much or little time passes, each frame is a single tick
+    /// for frame_time in &[0, 1, 1000, 60_000] {
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    ///     let actions: Vec<_> = game_loop.actions().collect();
+    ///     assert_eq!(
+    ///         actions,
+    ///         vec![
+    ///             FrameAction::Tick {
+    ///                 delta: game_loop.skip_ticks()
+    ///             },
+    ///             FrameAction::Render { interpolation: 1.0 },
+    ///         ]
+    ///     );
+    /// }
+    /// assert_eq!(game_loop.total_ticks(), 4);
+    ///
+    /// assert!(GameLoop::new_lockstep(24).is_ok());
+    /// ```
+    pub fn new_lockstep(tps: usize) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new().tps(tps).lockstep(true).build()
+    }
+
+    /// Create a new game loop that runs in real time, like `new`, but with the time between each
+    /// tick given directly rather than as ticks per second. This can express rates that don't
+    /// divide a second evenly, such as a tick every 16.666ms.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let game_loop = GameLoop::from_tick_duration(Duration::from_micros(16_666), 5).unwrap();
+    /// assert_eq!(game_loop.tps(), 60);
+    ///
+    /// // the tick duration must be non-zero
+    /// assert!(GameLoop::from_tick_duration(Duration::from_secs(0), 5).is_err());
+    ///
+    /// // a slow tick still reports at least 1 tick per second
+    /// let game_loop = GameLoop::from_tick_duration(Duration::from_secs(10), 5).unwrap();
+    /// assert_eq!(game_loop.tps(), 1);
+    /// assert_eq!(game_loop.skip_ticks(), Duration::from_secs(10));
+    /// ```
+    ///
+    /// Film runs at 24 frames per second, which a whole number of milliseconds can't express, but
+    /// the exact tick is kept to the nanosecond:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tick_duration(Duration::from_nanos(41_666_667))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(game_loop.tps(), 24);
+    ///
+    /// // a second of 10ms frames holds the immediate first tick and 23 more, where a 41ms tick
+    /// // would fit 24
+    /// for _ in 0..100 {
+    ///     clock.advance(Duration::from_millis(10));
+    ///     game_loop.actions().for_each(drop);
+    /// }
+    /// assert_eq!(game_loop.total_ticks(), 24);
+    /// ```
+    pub fn from_tick_duration(tick: Duration, max_frameskip: usize) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new()
+            .tick_duration(tick)
+            .max_frameskip(max_frameskip)
+            .build()
+    }
+
+    /// Create a new game loop that runs in real time, restoring the configuration and timing
+    /// state from a snapshot taken with `snapshot`. The values are validated in the same way as
+    /// `new`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// clock.advance(Duration::from_millis(130));
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // save, and load later on
+    /// let config = game_loop.snapshot();
+    /// let restored = GameLoop::from_config(config).unwrap();
+    ///
+    /// let restored_config = restored.snapshot();
+    /// assert_eq!(restored_config.next_tick, config.next_tick);
+    /// assert!(restored_config.game_time >= config.game_time);
+    /// assert_eq!(restored.total_ticks(), 3);
+    /// ```
+    pub fn from_config(config: GameLoopConfig) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::new().config(config).build()
+    }
+
+    /// Returns a builder for configuring a game loop beyond the options `new` offers.
+    pub fn builder() -> GameLoopBuilder {
+        GameLoopBuilder::new()
+    }
+
+    /// The instant the next tick is due, e.g. to wait until with winit's
+    /// `ControlFlow::WaitUntil`. See `time_until_next_tick`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ::gameloop::*;
+    /// # use std::time::Instant;
+    /// let game_loop = GameLoop::new(20, 5).unwrap();
+    /// loop {
+    ///     game_loop.actions().for_each(drop);
+    ///
+    ///     let deadline = game_loop.next_tick_instant();
+    ///     std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+    /// }
+    /// ```
+    ///
+    /// This is `time_until_next_tick` from now, which moves on by one tick as each is consumed:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.time_until_next_tick(), Duration::from_millis(50));
+    ///
+    /// // wait for the tick and consume it
+    /// clock.advance(Duration::from_millis(50));
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.time_until_next_tick(), Duration::from_millis(50));
+    /// ```
+    pub fn next_tick_instant(&self) -> Instant {
+        Instant::now() + self.time_until_next_tick()
+    }
+
+    /// The instant the next render is allowed under the render rate cap, or `None` if there is
+    /// no cap.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ::gameloop::*;
+    /// # use std::time::Instant;
+    /// let game_loop = GameLoop::with_max_fps(20, 5, 10).unwrap();
+    /// loop {
+    ///     game_loop.actions().for_each(drop);
+    ///
+    ///     let deadline = game_loop
+    ///         .next_render_instant()
+    ///         .unwrap()
+    ///         .min(game_loop.next_tick_instant());
+    ///     std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+    /// }
+    /// ```
+    ///
+    /// ```
+    /// # use ::gameloop::*;
+    /// // without a cap, a render is always allowed
+    /// assert!(GameLoop::new(20, 5).unwrap().next_render_instant().is_none());
+    /// ```
+    pub fn next_render_instant(&self) -> Option<Instant> {
+        self.time_until_next_render()
+            .map(|remaining| Instant::now() + remaining)
+    }
+}
+
+impl GameLoop {
+    /// The time between ticks at the given tick rate, as the loop computes it. This is truncated to
+    /// the nanosecond.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let interval = GameLoop::interval_from_tps(60).unwrap();
+    /// assert_eq!(interval, Duration::from_nanos(16_666_666));
+    ///
+    /// let interval = GameLoop::interval_from_tps(24).unwrap();
+    /// assert_eq!(interval, Duration::from_nanos(41_666_666));
+    /// assert!(GameLoop::interval_from_tps(0).is_err());
+    /// ```
+    pub fn interval_from_tps(tps: usize) -> Result<Duration, GameLoopError> {
+        if !valid_tps(tps) {
+            return Err(GameLoopError::BadTps(tps));
         }
 
-        let start_time = Instant::now();
-        let skip_ticks = 1000 / tps;
+        Ok(tick_interval(tps))
+    }
 
-        debug!(
-            "initialized with {} ticks/second ({}ms/tick), with a max frame skip of {}",
-            tps, skip_ticks, max_frameskip
-        );
+    /// The tick rate at the given time between ticks, as the loop reports it with `tps` when
+    /// created from a tick duration. This is rounded to the nearest whole tick per second, and is
+    /// at least 1, so an interval longer than two seconds gives 1 rather than an invalid rate.
+    /// Rounding undoes the truncation in `interval_from_tps`, so the two round-trip.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let tps = GameLoop::tps_from_interval(Duration::from_millis(50)).unwrap();
+    /// assert_eq!(tps, 20);
+    ///
+    /// let tps = GameLoop::tps_from_interval(Duration::from_millis(17)).unwrap();
+    /// assert_eq!(tps, 59);
+    /// assert!(GameLoop::tps_from_interval(Duration::from_secs(0)).is_err());
+    ///
+    /// // slow intervals are clamped to 1
+    /// assert_eq!(GameLoop::tps_from_interval(Duration::from_secs(2)).unwrap(), 1);
+    /// assert_eq!(GameLoop::tps_from_interval(Duration::from_secs(10)).unwrap(), 1);
+    ///
+    /// let interval = GameLoop::interval_from_tps(24).unwrap();
+    /// assert_eq!(GameLoop::tps_from_interval(interval).unwrap(), 24);
+    /// ```
+    pub fn tps_from_interval(interval: Duration) -> Result<usize, GameLoopError> {
+        if interval == Duration::from_secs(0) {
+            return Err(GameLoopError::BadTickDuration(interval));
+        }
 
-        Ok(Self {
-            start_time,
+        Ok(rate_of(interval))
+    }
+}
+
+impl<C: Clock> GameLoop<C> {
+    /// Create a new game loop driven by the given clock. See `new` for the meaning of the other
+    /// arguments.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// // the first tick is due immediately, then one every 50ms
+    /// clock.advance(Duration::from_millis(120));
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 3);
+    ///
+    /// // no time has passed, so nothing more is due
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 0);
+    /// ```
+    pub fn with_clock(tps: usize, max_frameskip: usize, clock: C) -> Result<Self, GameLoopError> {
+        GameLoopBuilder::with_clock(clock)
+            .tps(tps)
+            .max_frameskip(max_frameskip)
+            .build()
+    }
+
+    pub(crate) fn from_builder(builder: GameLoopBuilder<C>) -> Result<Self, GameLoopError> {
+        let GameLoopBuilder {
+            tick_rate,
             max_frameskip,
+            adaptive_frameskip,
+            interpolation,
+            clamp_interpolation,
+            interpolation_mode,
+            render_while_paused,
+            headless,
+            lockstep,
+            late_tick,
+            init,
+            frame_start,
+            coalesce_ticks,
+            max_fps,
+            max_catchup,
+            max_frame_time,
+            #[cfg(feature = "std")]
+            frame_time_window,
+            #[cfg(feature = "std")]
+            record_actions,
+            timing,
+            clock,
+        } = builder;
+
+        let (skip_ticks, tps) = match tick_rate {
+            TickRate::PerSecond(tps) if !valid_tps(tps) => return Err(GameLoopError::BadTps(tps)),
+            TickRate::PerSecond(tps) => (tick_interval(tps), tps),
+            TickRate::Interval(interval) if interval == Duration::from_secs(0) => {
+                return Err(GameLoopError::BadTickDuration(interval))
+            }
+            TickRate::Interval(interval) => (interval, rate_of(interval)),
+            TickRate::Restored { tps, .. } if !valid_tps(tps) => {
+                return Err(GameLoopError::BadTps(tps))
+            }
+            TickRate::Restored { interval, .. } if interval == Duration::from_secs(0) => {
+                return Err(GameLoopError::BadTickDuration(interval))
+            }
+            TickRate::Restored { tps, interval } => (interval, tps),
+        };
+
+        // an adaptive frame skip starts from the bottom of its range
+        let max_frameskip = match adaptive_frameskip {
+            Some((min, max)) if min < 1 || max < min => {
+                return Err(GameLoopError::BadFrameSkipRange(min, max))
+            }
+            Some((min, _)) => min,
+            None if max_frameskip < 1 => return Err(GameLoopError::BadFrameSkip(max_frameskip)),
+            None => max_frameskip,
+        };
+
+        if let Some(max_fps) = max_fps.filter(|max_fps| *max_fps < 1) {
+            return Err(GameLoopError::BadMaxFps(max_fps));
+        }
+
+        #[cfg(feature = "std")]
+        if frame_time_window < 1 {
+            return Err(GameLoopError::BadFrameTimeWindow(frame_time_window));
+        }
+
+        let start_time = clock.now();
+        let timing = timing.unwrap_or(TimingState {
+            game_time: Duration::from_secs(0),
+            next_tick: Duration::from_secs(0),
+            total_ticks: 0,
+        });
+
+        #[cfg(feature = "log")]
+        log::debug!(
+            "initialized with {:.2} ticks/second ({:?}/tick), with a max frame skip of {}",
+            1.0 / skip_ticks.as_secs_f64(),
             skip_ticks,
-            next_game_tick: Cell::new(0),
+            max_frameskip
+        );
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            tps = 1.0 / skip_ticks.as_secs_f64(),
+            ?skip_ticks,
+            max_frameskip,
+            "initialized game loop"
+        );
+
+        Ok(Self {
+            clock,
+            anchor_time: AtomicDuration::new(start_time),
+            anchor_game_time: AtomicDuration::new(timing.game_time),
+            speed: AtomicF64::new(1.0),
+            max_frameskip: AtomicUsize::new(max_frameskip),
+            adaptive_frameskip,
+            skip_ticks: AtomicDuration::new(skip_ticks),
+            tps: AtomicUsize::new(tps),
+            next_game_tick: AtomicDuration::new(timing.next_tick),
+            total_ticks: AtomicU64::new(timing.total_ticks),
+            interpolation,
+            clamp_interpolation,
+            interpolation_mode,
+            paused: AtomicBool::new(false),
+            render_while_paused,
+            headless,
+            lockstep,
+            late_tick,
+            init,
+            initialized: AtomicBool::new(false),
+            frame_start,
+            coalesce_ticks,
+            max_fps,
+            min_render_interval: max_fps.map(tick_interval),
+            last_render: AtomicOptionDuration::new(None),
+            render_start: AtomicOptionDuration::new(None),
+            render_duration: AtomicDuration::new(Duration::from_secs(0)),
+            stats: StatsTracker::new(start_time),
+            #[cfg(feature = "std")]
+            frame_times: FrameTimes::new(frame_time_window),
+            #[cfg(feature = "std")]
+            recording: if record_actions {
+                Some(Mutex::new(Vec::new()))
+            } else {
+                None
+            },
+            #[cfg(feature = "std")]
+            polled: Mutex::new(None),
+            behind: AtomicBool::new(false),
+            render_throttled: AtomicBool::new(false),
+            max_catchup,
+            max_frame_time,
         })
     }
 
@@ -115,42 +956,1720 @@ impl GameLoop {
     ///
     ///     for action in game_loop.actions() {
     ///         match action {
-    ///             FrameAction::Tick => /* simulate 1 game tick */
+    ///             FrameAction::Tick { .. } => /* simulate 1 game tick */
     /// # {},
     ///             FrameAction::Render { interpolation } => /* render the game state interpolated
     ///                                                         between previous and next tick */
     /// # {},
+    ///             FrameAction::TimeJumped { .. } => /* the game time jumped forward */
+    /// # {},
+    ///             FrameAction::LateTick => /* after the ticks, if enabled */
+    /// # {},
+    ///             FrameAction::Init => /* before the first tick, if enabled */
+    /// # {},
+    ///             FrameAction::FrameStart => /* before anything else, if enabled */
+    /// # {},
+    ///             FrameAction::Ticks { count, .. } => /* simulate `count` ticks at once, if
+    ///                                                    enabled */
+    /// # {},
+    ///             _ => /* variants added in later versions */
+    /// # {},
     ///         }
     ///     }
     /// # break;
     /// }
     ///```
-    pub fn actions(&self) -> impl Iterator<Item = FrameAction> + '_ {
-        FrameActions {
-            game_loop: self,
-            loops: 0,
-            rendered: false,
-        }
-    }
-
-    /// Milliseconds since the game started.
-    fn tick_count(&self) -> usize {
-        self.start_time.elapsed().as_millis() as usize
-    }
-
-    fn increment_next_game_tick(&self) {
-        let current = self.next_game_tick.get();
-        self.next_game_tick.set(current + self.skip_ticks);
-    }
-}
-
-/// Iterator of `FrameAction`s, returned by `GameLoop::actions`.
-pub struct FrameActions<'a> {
-    game_loop: &'a GameLoop,
-
-    loops: usize,
-    rendered: bool,
-}
+    ///
+    /// A tick is due as soon as the time it is scheduled for is reached:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// // the first tick is due immediately
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // the next is due exactly one tick later
+    /// clock.advance(Duration::from_millis(50));
+    /// assert!(matches!(
+    ///     game_loop.actions().next(),
+    ///     Some(FrameAction::Tick { .. })
+    /// ));
+    /// ```
+    ///
+    /// Every iteration ends with exactly one `FrameAction::Render`, even if no tick is due, unless
+    /// the loop is headless, paused without `render_while_paused`, or the render is held back by
+    /// `max_fps`:
+    /// ```
+    /// # use ::gameloop::*;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// // the first frame ticks, then renders exactly at that tick
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Render { interpolation }
+    ///     ] if interpolation == 0.0
+    /// ));
+    ///
+    /// // no time has passed, so there is nothing to tick but still a render
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [FrameAction::Render { interpolation }] if interpolation == 0.0
+    /// ));
+    /// ```
+    ///
+    /// The clock is read once, when this is called, and that time is used for every action of the
+    /// iteration. Time spent processing the actions is only seen by the next iteration:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::cell::Cell;
+    /// # use std::time::Duration;
+    /// /// Moves on 10ms every time it is read
+    /// #[derive(Default)]
+    /// struct CountingClock {
+    ///     reads: Cell<u32>,
+    /// }
+    ///
+    /// impl Clock for CountingClock {
+    ///     fn now(&self) -> Duration {
+    ///         self.reads.set(self.reads.get() + 1);
+    ///         Duration::from_millis(10) * self.reads.get()
+    ///     }
+    /// }
+    ///
+    /// let clock = CountingClock::default();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// let reads = clock.reads.get();
+    ///
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert_eq!(clock.reads.get(), reads + 1);
+    ///
+    /// // 10ms into the game, the first tick is done and the second is 40ms away
+    /// assert_eq!(actions.len(), 2);
+    /// assert_eq!(
+    ///     actions[0],
+    ///     FrameAction::Tick {
+    ///         delta: Duration::from_millis(50)
+    ///     }
+    /// );
+    /// match actions[1] {
+    ///     FrameAction::Render { interpolation } => assert!((interpolation - 0.2).abs() < 1e-9),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    ///
+    /// The loop is only borrowed immutably, so it can be changed from inside the iteration, e.g. to
+    /// change the tick rate in response to a tick. Such a change takes effect from the next
+    /// iteration, so the ticks of an iteration all have the same duration:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// clock.advance(Duration::from_millis(200));
+    /// let mut deltas = vec![];
+    /// for action in game_loop.actions() {
+    ///     if let FrameAction::Tick { delta } = action {
+    ///         deltas.push(delta);
+    ///         game_loop.set_tps(10).unwrap();
+    ///     }
+    /// }
+    /// assert_eq!(deltas, vec![Duration::from_millis(50)]);
+    ///
+    /// let next = game_loop.actions().next();
+    /// assert_eq!(
+    ///     next,
+    ///     Some(FrameAction::Tick {
+    ///         delta: Duration::from_millis(100)
+    ///     })
+    /// );
+    /// ```
+    pub fn actions(&self) -> FrameActions<'_, C> {
+        FrameActions {
+            game_loop: self,
+            state: FrameState::new(self.clock.now(), self.skip_ticks.get()),
+        }
+    }
+
+    /// Returns the next action of the current iteration, as `actions()` would, or `None` once the
+    /// iteration is complete. The next call after that starts a new iteration, so this can be
+    /// called repeatedly instead of iterating `actions()`, e.g. from a callback or across an FFI
+    /// boundary. Requires the `std` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let iterated = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// let polled = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// for frame_time in &[0, 10, 120, 30, 400, 5] {
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    ///
+    ///     let expected: Vec<_> = iterated.actions().collect();
+    ///     let mut actions = vec![];
+    ///     while let Some(action) = polled.poll() {
+    ///         actions.push(action);
+    ///     }
+    ///     assert_eq!(actions, expected);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn poll(&self) -> Option<FrameAction> {
+        let mut polled = self.polled.lock().unwrap_or_else(PoisonError::into_inner);
+        let state = polled
+            .take()
+            .unwrap_or_else(|| FrameState::new(self.clock.now(), self.skip_ticks.get()));
+
+        let mut actions = FrameActions {
+            game_loop: self,
+            state,
+        };
+        let action = actions.next();
+        if action.is_some() {
+            *polled = Some(actions.state);
+        }
+        action
+    }
+
+    /// Emits exactly `n` ticks followed by a render, unless headless, as fast as possible and
+    /// regardless of the clock, `max_frameskip` or whether the loop is paused. This is intended
+    /// for running a simulation headlessly and deterministically, e.g. in benchmarks or
+    /// regression tests.
+    ///
+    /// The render's interpolation is always 1.0, as the game state is exactly at the last tick.
+    /// The ticks are taken from the schedule, so if the loop is stepped ahead of the clock,
+    /// `actions()` yields no more ticks until the clock catches up.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// let game_loop = GameLoop::new(60, 5).unwrap();
+    ///
+    /// let actions: Vec<_> = game_loop.step_n_ticks(10_000).collect();
+    /// let ticks = actions
+    ///     .iter()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 10_000);
+    /// assert_eq!(game_loop.total_ticks(), 10_000);
+    /// assert_eq!(
+    ///     actions.last(),
+    ///     Some(&FrameAction::Render { interpolation: 1.0 })
+    /// );
+    /// ```
+    pub fn step_n_ticks(&self, n: usize) -> StepActions<'_, C> {
+        StepActions {
+            game_loop: self,
+            remaining: n,
+            late_tick: self.late_tick && n > 0,
+            rendered: false,
+        }
+    }
+
+    /// Runs a single iteration of the game loop at once, returning how many ticks to simulate and
+    /// the interpolation to render with, if any. This is equivalent to collecting `actions()`,
+    /// for games that would rather not match on each action.
+    ///
+    /// Call this once per game loop iteration.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.advance();
+    ///
+    /// clock.advance(Duration::from_millis(110));
+    /// let outcome = game_loop.advance();
+    /// assert_eq!(outcome.ticks, 2);
+    /// assert!((outcome.render.unwrap() - 0.2).abs() < 1e-9);
+    ///
+    /// // nothing due, but still rendered
+    /// assert_eq!(game_loop.advance().ticks, 0);
+    /// ```
+    pub fn advance(&self) -> FrameOutcome {
+        let mut actions = self.actions();
+        actions.by_ref().for_each(drop);
+        actions.outcome()
+    }
+
+    /// Runs a single iteration of the game loop, calling `tick` for each `FrameAction::Tick` and
+    /// each tick of a `FrameAction::Ticks`, and `render` with the interpolation of each
+    /// `FrameAction::Render`. This is equivalent to
+    /// matching on each item of `actions()`, ignoring any `FrameAction::TimeJumped`,
+    /// `FrameAction::LateTick`, `FrameAction::Init` and `FrameAction::FrameStart`.
+    ///
+    /// Call this once per game loop iteration.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// let mut ticks = 0;
+    /// let mut renders = 0;
+    ///
+    /// clock.advance(Duration::from_millis(120));
+    /// game_loop.run(|| ticks += 1, |_interpolation| renders += 1);
+    ///
+    /// assert_eq!(ticks, 3);
+    /// assert_eq!(renders, 1);
+    /// ```
+    pub fn run<T, R>(&self, mut tick: T, mut render: R)
+    where
+        T: FnMut(),
+        R: FnMut(f64),
+    {
+        for action in self.actions() {
+            match action {
+                FrameAction::Tick { .. } => tick(),
+                FrameAction::Ticks { count, .. } => (0..count).for_each(|_| tick()),
+                FrameAction::Render { interpolation } => render(interpolation),
+                FrameAction::TimeJumped { .. }
+                | FrameAction::LateTick
+                | FrameAction::Init
+                | FrameAction::FrameStart => {}
+            }
+        }
+    }
+
+    /// Runs the game loop until `should_quit` returns true, which is checked before each
+    /// iteration. Each iteration calls `tick` and `render` as `run` does, then sleeps until the
+    /// next tick is due or the next render is allowed, whichever is sooner. Requires the `std`
+    /// feature.
+    ///
+    /// Without a render rate cap, this sleeps until the next tick, so there is one render per
+    /// tick. Set `GameLoopBuilder::max_fps` to render more often, with interpolation between
+    /// ticks. `run` and `actions()` never sleep, for those that want renders uncapped.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::cell::Cell;
+    /// let game_loop = GameLoop::new(100, 5).unwrap();
+    ///
+    /// let ticks = Cell::new(0);
+    /// let renders = Cell::new(0);
+    /// game_loop.run_until(
+    ///     || ticks.get() >= 10,
+    ///     || ticks.set(ticks.get() + 1),
+    ///     |_interpolation| renders.set(renders.get() + 1),
+    /// );
+    ///
+    /// // sleeps between ticks rather than spinning on renders
+    /// assert_eq!(ticks.get(), 10);
+    /// assert!(renders.get() >= 1 && renders.get() <= 20);
+    /// ```
+    ///
+    /// With a render rate cap, it wakes up for renders between ticks too:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::cell::Cell;
+    /// let game_loop = GameLoop::builder().tps(1000).max_fps(500).build().unwrap();
+    ///
+    /// let ticks = Cell::new(0);
+    /// let renders = Cell::new(0);
+    /// game_loop.run_until(
+    ///     || ticks.get() >= 10,
+    ///     || ticks.set(ticks.get() + 1),
+    ///     |_interpolation| renders.set(renders.get() + 1),
+    /// );
+    ///
+    /// assert_eq!(ticks.get(), 10);
+    /// assert!(renders.get() >= 1);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn run_until<Q, T, R>(&self, mut should_quit: Q, mut tick: T, mut render: R)
+    where
+        Q: FnMut() -> bool,
+        T: FnMut(),
+        R: FnMut(f64),
+    {
+        while !should_quit() {
+            self.run(&mut tick, &mut render);
+            std::thread::sleep(self.time_until_next_frame());
+        }
+    }
+
+    /// The number of game ticks per second, as given to the constructor, builder or `set_tps`.
+    /// If the tick duration was given instead, this is derived from it and rounded to the nearest
+    /// whole tick, but never below 1, even for ticks more than two seconds apart.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let game_loop = GameLoop::new(60, 5).unwrap();
+    /// assert_eq!(game_loop.tps(), 60);
+    /// assert_eq!(game_loop.max_frameskip(), 5);
+    /// assert_eq!(game_loop.skip_ticks(), Duration::from_nanos(16_666_666));
+    ///
+    /// // exact, even where the rounded tick duration alone is ambiguous
+    /// let game_loop = GameLoop::new(70_001, 5).unwrap();
+    /// assert_eq!(game_loop.tps(), 70_001);
+    /// game_loop.set_tps(70_002).unwrap();
+    /// assert_eq!(game_loop.tps(), 70_002);
+    /// ```
+    pub fn tps(&self) -> usize {
+        self.tps.load(Ordering::Relaxed)
+    }
+
+    /// Maximum number of consecutive ticks before a render is mandatory. With
+    /// `GameLoopBuilder::adaptive_frameskip`, this is the current value within the range.
+    pub fn max_frameskip(&self) -> usize {
+        self.max_frameskip.load(Ordering::Relaxed)
+    }
+
+    /// The render rate below which the game slows down, i.e. the tick rate divided by
+    /// `max_frameskip`. As [deWiTTERS](https://dewitters.com/dewitters-gameloop/) explains:
+    /// > When running on slow hardware, the framerate can drop until the game update loop will
+    /// > reach MAX_FRAMESKIP. In practice this means that when our render FPS drops below 5
+    /// > (= FRAMES_PER_SECOND / MAX_FRAMESKIP), the actual game will slow down.
+    ///
+    /// This can be shown as a warning to players whose hardware can't keep up.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// let game_loop = GameLoop::new(60, 5).unwrap();
+    /// assert!((game_loop.min_render_fps() - 12.0).abs() < 1e-6);
+    /// ```
+    pub fn min_render_fps(&self) -> f64 {
+        1.0 / self.skip_ticks.get().as_secs_f64() / self.max_frameskip() as f64
+    }
+
+    /// Maximum number of renders per second, or `None` if unlimited.
+    pub fn max_fps(&self) -> Option<usize> {
+        self.max_fps
+    }
+
+    /// Time between each game tick. This is tracked to the nanosecond, so tick rates above 1000
+    /// per second keep their sub-millisecond timing.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(2000)
+    ///     .max_frameskip(100)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(game_loop.skip_ticks(), Duration::from_micros(500));
+    ///
+    /// // 2 ticks per millisecond, after the first at 0ms, without ever saturating max_frameskip
+    /// game_loop.actions().for_each(drop);
+    /// for _ in 0..1000 {
+    ///     clock.advance(Duration::from_millis(1));
+    ///     let ticks = game_loop
+    ///         .actions()
+    ///         .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///         .count();
+    ///     assert_eq!(ticks, 2);
+    ///     assert!(!game_loop.is_behind());
+    /// }
+    /// assert_eq!(game_loop.total_ticks(), 2001);
+    /// ```
+    pub fn skip_ticks(&self) -> Duration {
+        self.skip_ticks.get()
+    }
+
+    /// The number of `FrameAction::Tick`s emitted since the game started, across all calls to
+    /// `actions()`. This is zeroed by `reset`.
+    ///
+    /// The count is incremented before each tick is yielded, so while handling a tick this is its
+    /// number, starting from 1. This makes it suitable as a deterministic tick index for lockstep
+    /// networking, replays or scheduling.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// // simulate 100 seconds of 10ms frames
+    /// for _ in 0..10_000 {
+    ///     clock.advance(Duration::from_millis(10));
+    ///     game_loop.actions().for_each(drop);
+    /// }
+    ///
+    /// assert!((game_loop.total_ticks() as i64 - 2000).abs() <= 1);
+    /// ```
+    ///
+    /// Each tick sees its own number, across frames:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// let mut seen = vec![];
+    /// for frame_time in &[0, 120, 50] {
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    ///     for action in game_loop.actions() {
+    ///         if let FrameAction::Tick { .. } = action {
+    ///             seen.push(game_loop.total_ticks());
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(seen, vec![1, 2, 3, 4]);
+    /// ```
+    ///
+    /// Time is tracked with nanosecond precision in 64 bits, so long-running loops keep ticking
+    /// correctly well past the point a 32-bit millisecond counter would wrap (~49.7 days):
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(1)
+    ///     .max_frameskip(usize::MAX)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // one tick per second, including the first at 0s
+    /// let fifty_days = Duration::from_secs(50 * 24 * 60 * 60);
+    /// clock.advance(fifty_days);
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.total_ticks(), fifty_days.as_secs() + 1);
+    ///
+    /// clock.advance(Duration::from_secs(1));
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.total_ticks(), fifty_days.as_secs() + 2);
+    /// ```
+    pub fn total_ticks(&self) -> u64 {
+        self.total_ticks.load(Ordering::Relaxed)
+    }
+
+    /// Measured render and tick rates over the last second of real time, for diagnostics. See
+    /// `LoopStats` for how these are estimated.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 2, &clock).unwrap();
+    ///
+    /// // each frame takes far longer than a tick, so the game can't keep up
+    /// for _ in 0..20 {
+    ///     clock.advance(Duration::from_millis(200));
+    ///     game_loop.actions().for_each(drop);
+    /// }
+    ///
+    /// let stats = game_loop.stats();
+    /// assert!(stats.tps < 20.0);
+    /// assert!(stats.frameskip_saturated > 0);
+    /// ```
+    pub fn stats(&self) -> LoopStats {
+        self.stats.stats(self.clock.now())
+    }
+
+    /// The mean real time between the most recent renders, for a steadier readout than the
+    /// time of a single frame. The number of renders averaged over is set with
+    /// `GameLoopBuilder::frame_time_window`, 60 by default. This is zero until there have been
+    /// two renders, and is cleared by `reset`. Requires the `std` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .frame_time_window(4)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(game_loop.average_frame_time(), Duration::from_secs(0));
+    ///
+    /// for frame_time in &[10, 10, 10, 10, 20, 20] {
+    ///     game_loop.actions().for_each(drop);
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    /// }
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // the last 4 frames
+    /// assert_eq!(game_loop.average_frame_time(), Duration::from_millis(15));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn average_frame_time(&self) -> Duration {
+        self.frame_times.average()
+    }
+
+    /// Whether the game is running behind, i.e. the last iteration of `actions()` emitted
+    /// `max_frameskip` ticks and more were still due. When this happens consistently, the game
+    /// visibly slows down, and lowering the rendering quality may help it keep up.
+    ///
+    /// This is updated once the ticks of an iteration are exhausted, and cleared by the first
+    /// iteration that catches up.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 2, &clock).unwrap();
+    ///
+    /// // a long stall means more than max_frameskip ticks are due
+    /// clock.advance(Duration::from_millis(500));
+    /// game_loop.actions().for_each(drop);
+    /// assert!(game_loop.is_behind());
+    ///
+    /// // without any more stalls, the backlog is worked through and the flag clears
+    /// let mut iterations = 1;
+    /// while game_loop.is_behind() {
+    ///     game_loop.actions().for_each(drop);
+    ///     iterations += 1;
+    /// }
+    /// assert_eq!(iterations, 6);
+    /// assert_eq!(game_loop.total_ticks(), 11);
+    /// ```
+    pub fn is_behind(&self) -> bool {
+        self.behind.load(Ordering::Relaxed)
+    }
+
+    /// Whether the last iteration of `actions()` held back its render because it was too soon
+    /// after the last under `max_fps`, as opposed to not rendering at all, e.g. when headless.
+    /// This can be used to skip work that only matters when drawing, such as acquiring the next
+    /// swapchain image.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .max_fps(10)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut throttled = vec![];
+    /// for _ in 0..6 {
+    ///     game_loop.actions().for_each(drop);
+    ///     throttled.push(game_loop.render_skipped_last_frame());
+    ///     clock.advance(Duration::from_millis(40));
+    /// }
+    ///
+    /// // a render at most every 100ms
+    /// assert_eq!(throttled, vec![false, true, true, false, true, true]);
+    /// ```
+    pub fn render_skipped_last_frame(&self) -> bool {
+        self.render_throttled.load(Ordering::Relaxed)
+    }
+
+    /// Marks the start of rendering, to measure how long it takes with `end_render`. This is
+    /// optional, and costs nothing if unused.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// game_loop.begin_render();
+    /// clock.advance(Duration::from_millis(20));
+    /// game_loop.end_render();
+    /// assert_eq!(game_loop.last_render_duration(), Duration::from_millis(20));
+    /// assert!(!game_loop.budget_exceeded());
+    ///
+    /// // a hitch, longer than the 50ms between ticks
+    /// game_loop.begin_render();
+    /// clock.advance(Duration::from_millis(80));
+    /// game_loop.end_render();
+    /// assert!(game_loop.budget_exceeded());
+    /// ```
+    pub fn begin_render(&self) {
+        self.render_start.set(Some(self.clock.now()));
+    }
+
+    /// Marks the end of rendering, recording the time since `begin_render`. Does nothing if
+    /// `begin_render` wasn't called first.
+    pub fn end_render(&self) {
+        if let Some(start) = self.render_start.get() {
+            self.render_start.set(None);
+            let duration = self.clock.now().saturating_sub(start);
+            self.render_duration.set(duration);
+        }
+    }
+
+    /// How long the last render took, measured between `begin_render` and `end_render`. This is
+    /// zero if they have never been called.
+    pub fn last_render_duration(&self) -> Duration {
+        self.render_duration.get()
+    }
+
+    /// Whether the last render took longer than the time between ticks, which will cause a
+    /// visible hitch. See `begin_render`.
+    pub fn budget_exceeded(&self) -> bool {
+        self.last_render_duration() > self.skip_ticks.get()
+    }
+
+    /// Takes a snapshot of the configuration and timing state, which can be saved and restored
+    /// with `from_config`. See `GameLoopConfig` for details.
+    pub fn snapshot(&self) -> GameLoopConfig {
+        GameLoopConfig {
+            tps: self.tps(),
+            tick_duration: self.skip_ticks.get(),
+            max_frameskip: self.max_frameskip(),
+            max_fps: self.max_fps,
+            game_time: self.tick_count(),
+            next_tick: self.next_game_tick.get(),
+            total_ticks: self.total_ticks.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Changes the number of game ticks per second, which must be >= 1 and at most one per
+    /// nanosecond.
+    ///
+    /// The next scheduled tick is moved so that the progress towards it is kept, relative to the
+    /// new tick rate. Any ticks that were already overdue are dropped, so the change never causes
+    /// a burst of catch-up ticks.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(30, 5, &clock).unwrap();
+    ///
+    /// let ticks = || {
+    ///     game_loop
+    ///         .actions()
+    ///         .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///         .count()
+    /// };
+    ///
+    /// // fall behind by several ticks, then speed up without a burst of ticks
+    /// clock.advance(Duration::from_millis(150));
+    /// game_loop.set_tps(60).unwrap();
+    /// clock.advance(Duration::from_millis(10));
+    /// assert_eq!(ticks(), 1);
+    ///
+    /// // slow down 60% of the way to the next tick, which keeps that progress
+    /// game_loop.set_tps(10).unwrap();
+    /// clock.advance(Duration::from_millis(39));
+    /// assert_eq!(ticks(), 0);
+    /// clock.advance(Duration::from_millis(2));
+    /// assert_eq!(ticks(), 1);
+    ///
+    /// assert!(game_loop.set_tps(0).is_err());
+    /// ```
+    pub fn set_tps(&self, tps: usize) -> Result<(), GameLoopError> {
+        if !valid_tps(tps) {
+            return Err(GameLoopError::BadTps(tps));
+        }
+
+        let now = self.tick_count();
+        let old_skip_ticks = self.skip_ticks.get();
+        let new_skip_ticks = tick_interval(tps);
+
+        // fraction of the way through the current tick, capped so overdue ticks are dropped
+        let progress = (now + old_skip_ticks)
+            .saturating_sub(self.next_game_tick.get())
+            .as_secs_f64()
+            / old_skip_ticks.as_secs_f64();
+        let remaining = new_skip_ticks.mul_f64(1.0 - progress.min(1.0));
+
+        self.skip_ticks.set(new_skip_ticks);
+        self.tps.store(tps, Ordering::Relaxed);
+        self.next_game_tick.set(now + remaining);
+
+        #[cfg(feature = "log")]
+        log::debug!(
+            "changed to {} ticks/second ({:?}/tick)",
+            tps,
+            new_skip_ticks
+        );
+        #[cfg(feature = "tracing")]
+        tracing::debug!(tps, skip_ticks = ?new_skip_ticks, "changed tick rate");
+        Ok(())
+    }
+
+    /// Changes the maximum number of consecutive ticks before a render is mandatory, which must
+    /// be at least 1, e.g. to allow more catch-up when the game detects it is persistently slowing
+    /// down. An iteration of `actions()` that is in progress uses the new value for its remaining
+    /// ticks.
+    ///
+    /// With `GameLoopBuilder::adaptive_frameskip`, the value carries on adapting from here.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 10, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// let ticks_after_stall = || {
+    ///     clock.advance(Duration::from_secs(1));
+    ///     let ticks = game_loop
+    ///         .actions()
+    ///         .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///         .count();
+    ///     game_loop.reset();
+    ///     game_loop.actions().for_each(drop);
+    ///     ticks
+    /// };
+    ///
+    /// assert_eq!(ticks_after_stall(), 10);
+    /// game_loop.set_max_frameskip(3).unwrap();
+    /// assert_eq!(ticks_after_stall(), 3);
+    ///
+    /// assert!(game_loop.set_max_frameskip(0).is_err());
+    /// ```
+    pub fn set_max_frameskip(&self, max_frameskip: usize) -> Result<(), GameLoopError> {
+        if max_frameskip < 1 {
+            return Err(GameLoopError::BadFrameSkip(max_frameskip));
+        }
+
+        self.max_frameskip.store(max_frameskip, Ordering::Relaxed);
+        debug!("changed max frame skip to {}", max_frameskip);
+        Ok(())
+    }
+
+    /// Pauses the game, freezing the game time until `resume` is called. While paused,
+    /// `actions()` never yields `FrameAction::Tick`, and only yields `FrameAction::Render` if
+    /// enabled with `GameLoopBuilder::render_while_paused` (the default).
+    ///
+    /// Does nothing if already paused.
+    pub fn pause(&self) {
+        if !self.is_paused() {
+            self.anchor();
+            self.paused.store(true, Ordering::Relaxed);
+            debug!("paused");
+        }
+    }
+
+    /// Resumes the game after `pause`. The time spent paused is skipped over, and any ticks that
+    /// were overdue when paused are dropped, so no burst of catch-up ticks follows.
+    ///
+    /// Does nothing if not paused.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// game_loop.pause();
+    /// assert!(game_loop.is_paused());
+    ///
+    /// // a long time passes in a menu, but only renders are produced
+    /// clock.advance(Duration::from_secs(10));
+    /// assert!(game_loop
+    ///     .actions()
+    ///     .all(|action| matches!(action, FrameAction::Render { .. })));
+    ///
+    /// game_loop.resume();
+    /// assert!(!game_loop.is_paused());
+    ///
+    /// // no storm of catch-up ticks
+    /// clock.advance(Duration::from_millis(40));
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 1);
+    /// ```
+    pub fn resume(&self) {
+        if self.is_paused() {
+            self.anchor();
+            self.paused.store(false, Ordering::Relaxed);
+
+            let now = self.tick_count();
+            if self.next_game_tick.get() < now {
+                self.next_game_tick.set(now);
+            }
+
+            debug!("resumed");
+        }
+    }
+
+    /// Marks the real start of the game, e.g. once assets have loaded and the window is open. The
+    /// game time is measured from when the loop was created, so without this, any delay before
+    /// the first iteration of `actions()` is caught up on with a burst of ticks.
+    ///
+    /// This restarts the game time from zero, with the first tick due immediately. It is the same
+    /// as `reset`, and is intended to be called once, just before the first iteration.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// // loading takes a while
+    /// clock.advance(Duration::from_secs(2));
+    /// game_loop.start();
+    ///
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 1);
+    /// ```
+    pub fn start(&self) {
+        self.reset();
+    }
+
+    /// Restarts the loop as if it had just been created, e.g. after a level transition. Any
+    /// pending backlog of ticks is discarded, and `total_ticks` and `stats` are zeroed. If paused,
+    /// the loop stays paused. See `rebase` to only discard the backlog.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// // run for a while, then fall far behind during a loading screen
+    /// clock.advance(Duration::from_millis(30));
+    /// game_loop.actions().for_each(drop);
+    /// clock.advance(Duration::from_secs(5));
+    ///
+    /// game_loop.reset();
+    ///
+    /// // behaves like a fresh loop: just the first tick and a render, no backlog
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [FrameAction::Tick { .. }, FrameAction::Render { .. }]
+    /// ));
+    /// assert_eq!(game_loop.total_ticks(), 1);
+    /// ```
+    pub fn reset(&self) {
+        let now = self.clock.now();
+        self.anchor_time.set(now);
+        self.stats.reset(now);
+        #[cfg(feature = "std")]
+        self.frame_times.reset();
+        self.anchor_game_time.set(Duration::from_secs(0));
+        self.next_game_tick.set(Duration::from_secs(0));
+        self.total_ticks.store(0, Ordering::Relaxed);
+        self.behind.store(false, Ordering::Relaxed);
+
+        debug!("reset");
+    }
+
+    /// Discards any pending backlog of ticks, e.g. after a loading screen, so the next tick is due
+    /// immediately rather than caught up on with a burst. Unlike `reset`, the game time,
+    /// `total_ticks` and `stats` carry on from where they were, so this suits a game that
+    /// continues rather than restarts. If no ticks are overdue, this does nothing.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// clock.advance(Duration::from_millis(120));
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.total_ticks(), 3);
+    ///
+    /// // stall for a while, then carry on without catching up
+    /// clock.advance(Duration::from_secs(5));
+    /// game_loop.rebase();
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [FrameAction::Tick { .. }, FrameAction::Render { .. }]
+    /// ));
+    /// assert_eq!(game_loop.total_ticks(), 4);
+    ///
+    /// // whereas a reset starts the counters over
+    /// game_loop.reset();
+    /// assert_eq!(game_loop.total_ticks(), 0);
+    /// ```
+    pub fn rebase(&self) {
+        let now = self.tick_count();
+        let next_tick = self.next_game_tick.get();
+        if now > next_tick {
+            self.next_game_tick.set(now);
+            debug!("rebased, discarding {:?} of backlog", now - next_tick);
+        }
+
+        self.behind.store(false, Ordering::Relaxed);
+    }
+
+    /// Pushes the schedule back by the given amount of game time, so the ticks that would have
+    /// been due in it are forgiven rather than run, e.g. to exclude a known stall such as a
+    /// synchronous load. Unlike `pause`, the game time carries on, and unlike `rebase`, only that
+    /// amount is skipped. If it is more than the backlog, the next tick is delayed by the rest.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // a second long load, of which 800ms is known dead time
+    /// clock.advance(Duration::from_secs(1));
+    /// game_loop.skip_time(Duration::from_millis(800));
+    ///
+    /// // 16 of the 20 ticks are skipped
+    /// assert_eq!(game_loop.pending_ticks(), 4);
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.total_ticks(), 5);
+    /// assert!(!game_loop.is_behind());
+    /// ```
+    pub fn skip_time(&self, amount: Duration) {
+        self.next_game_tick.add(amount);
+        debug!("skipped {:?} of game time", amount);
+    }
+
+    /// Changes the rate at which game time passes relative to real time, e.g. 0.5 for slow
+    /// motion or 2.0 to fast forward. The tick rate and interpolation are in terms of game time,
+    /// so at 2.0 twice as many ticks are emitted per real second.
+    ///
+    /// A speed of 0.0 stops ticks entirely, but unlike `pause`, renders are unaffected. Negative
+    /// and non-finite speeds are rejected.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(10)
+    ///     .max_frameskip(100)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut ticks_in_a_second = || {
+    ///     game_loop.actions().for_each(drop);
+    ///     let before = game_loop.total_ticks();
+    ///     clock.advance(Duration::from_secs(1));
+    ///     game_loop.actions().for_each(drop);
+    ///     game_loop.total_ticks() - before
+    /// };
+    ///
+    /// assert_eq!(ticks_in_a_second(), 10);
+    ///
+    /// game_loop.set_speed(0.5).unwrap();
+    /// assert_eq!(ticks_in_a_second(), 5);
+    ///
+    /// game_loop.set_speed(2.0).unwrap();
+    /// assert_eq!(ticks_in_a_second(), 20);
+    ///
+    /// game_loop.set_speed(0.0).unwrap();
+    /// assert_eq!(ticks_in_a_second(), 0);
+    ///
+    /// // but still renders
+    /// assert!(matches!(
+    ///     game_loop.actions().last(),
+    ///     Some(FrameAction::Render { .. })
+    /// ));
+    ///
+    /// assert!(game_loop.set_speed(-1.0).is_err());
+    /// ```
+    pub fn set_speed(&self, speed: f64) -> Result<(), GameLoopError> {
+        if !(speed >= 0.0 && speed.is_finite()) {
+            return Err(GameLoopError::BadSpeed(speed));
+        }
+
+        self.anchor();
+        self.speed.set(speed);
+
+        debug!("changed speed to {}x", speed);
+        Ok(())
+    }
+
+    /// Scales the rate at which game time passes, e.g. for slow motion. This is the same as
+    /// `set_speed`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(10, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// game_loop.set_time_scale(0.5).unwrap();
+    /// assert_eq!(game_loop.speed(), 0.5);
+    ///
+    /// // a tick every 200ms of real time
+    /// clock.advance(Duration::from_millis(200));
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.total_ticks(), 2);
+    ///
+    /// assert!(game_loop.set_time_scale(-1.0).is_err());
+    /// ```
+    pub fn set_time_scale(&self, scale: f64) -> Result<(), GameLoopError> {
+        self.set_speed(scale)
+    }
+
+    /// The rate at which game time passes relative to real time.
+    #[doc(alias = "time_scale")]
+    pub fn speed(&self) -> f64 {
+        self.speed.get()
+    }
+
+    /// Time until the next tick is due, or zero if it is already overdue. Sleeping for this long
+    /// at the end of each loop iteration avoids spinning the CPU.
+    ///
+    /// While paused, the game time is frozen, so this does not decrease until resumed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ::gameloop::*;
+    /// let game_loop = GameLoop::new(20, 5).unwrap();
+    /// loop {
+    ///     for _action in game_loop.actions() {
+    ///         // ...
+    ///     }
+    ///
+    ///     std::thread::sleep(game_loop.time_until_next_tick());
+    /// }
+    /// ```
+    ///
+    /// Once all due ticks have been consumed, the next is at most one tick away:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// clock.advance(Duration::from_millis(110));
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// let wait = game_loop.time_until_next_tick();
+    /// assert!(wait > Duration::from_secs(0));
+    /// assert!(wait <= game_loop.skip_ticks());
+    ///
+    /// // overdue ticks don't underflow
+    /// clock.advance(Duration::from_secs(1));
+    /// assert_eq!(game_loop.time_until_next_tick(), Duration::from_secs(0));
+    /// ```
+    pub fn time_until_next_tick(&self) -> Duration {
+        if self.lockstep {
+            return Duration::from_secs(0);
+        }
+
+        let remaining = self.next_game_tick.get().saturating_sub(self.tick_count());
+
+        // convert from game time to real time
+        let speed = self.speed.get();
+        if speed > 0.0 {
+            remaining.div_f64(speed)
+        } else {
+            remaining
+        }
+    }
+
+    /// Waits until the next tick is due, without blocking the thread, as an async alternative to
+    /// sleeping for `time_until_next_tick`. This resolves immediately if a tick is already
+    /// overdue. Requires the `async` feature, and must be awaited within a tokio runtime with
+    /// the time driver enabled.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ::gameloop::*;
+    /// # async fn run() {
+    /// let game_loop = GameLoop::new(20, 5).unwrap();
+    /// loop {
+    ///     for _action in game_loop.actions() {
+    ///         // ...
+    ///     }
+    ///
+    ///     game_loop.wait_next_tick().await;
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// With tokio's time paused and a `ManualClock`, the wait is exact:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// # use tokio::time::Instant;
+    /// # let runtime = tokio::runtime::Builder::new_current_thread()
+    /// #     .enable_time()
+    /// #     .start_paused(true)
+    /// #     .build()
+    /// #     .unwrap();
+    /// # runtime.block_on(async {
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// let start = Instant::now();
+    /// game_loop.wait_next_tick().await;
+    /// assert_eq!(start.elapsed(), Duration::from_millis(50));
+    ///
+    /// clock.advance(Duration::from_millis(50));
+    /// assert!(matches!(
+    ///     game_loop.actions().next(),
+    ///     Some(FrameAction::Tick { .. })
+    /// ));
+    ///
+    /// // a tick is already overdue
+    /// clock.advance(Duration::from_millis(60));
+    /// let start = Instant::now();
+    /// game_loop.wait_next_tick().await;
+    /// assert_eq!(start.elapsed(), Duration::from_secs(0));
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn wait_next_tick(&self) {
+        let remaining = self.time_until_next_tick();
+        if remaining > Duration::from_secs(0) {
+            tokio::time::sleep(remaining).await;
+        }
+    }
+
+    /// The interpolation that a `FrameAction::Render` would have if it were emitted now,
+    /// respecting `interpolation` and `clamp_interpolation`. This is useful when rendering on a
+    /// different thread to the one iterating `actions()`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// clock.advance(Duration::from_millis(10));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert_eq!(
+    ///     actions.last(),
+    ///     Some(&FrameAction::Render {
+    ///         interpolation: game_loop.current_interpolation()
+    ///     })
+    /// );
+    ///
+    /// // 12ms into the 50ms between ticks
+    /// clock.advance(Duration::from_millis(2));
+    /// assert!((game_loop.current_interpolation() - 0.24).abs() < 1e-9);
+    /// ```
+    pub fn current_interpolation(&self) -> f64 {
+        self.interpolation_at(self.tick_count())
+    }
+
+    /// The number of ticks that the next iteration of `actions()` would emit if it started now,
+    /// limited by `max_frameskip`. This doesn't consume any ticks. See
+    /// `FrameActions::pending_ticks`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    /// assert_eq!(game_loop.pending_ticks(), 0);
+    ///
+    /// // stall for 3 and a half ticks
+    /// clock.advance(Duration::from_millis(175));
+    /// assert_eq!(game_loop.pending_ticks(), 3);
+    /// assert_eq!(game_loop.pending_ticks(), 3);
+    ///
+    /// let ticks = game_loop
+    ///     .actions()
+    ///     .filter(|action| matches!(action, FrameAction::Tick { .. }))
+    ///     .count();
+    /// assert_eq!(ticks, 3);
+    /// assert_eq!(game_loop.pending_ticks(), 0);
+    /// ```
+    pub fn pending_ticks(&self) -> usize {
+        self.actions().pending_ticks()
+    }
+
+    /// Time the game has been running, e.g. for timers and animations that don't depend on ticks.
+    /// This excludes time spent paused, and is scaled by the speed.
+    ///
+    /// This is game time, which is what ticks are scheduled against, rather than wall time, which
+    /// is what the clock reads. The two only agree while the game has never been paused or run at
+    /// a speed other than 1.0. Wall time, e.g. for frame timings, should be read from the clock.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let game_loop = GameLoop::new(20, 5).unwrap();
+    ///
+    /// std::thread::sleep(Duration::from_millis(20));
+    /// assert!(game_loop.elapsed() >= Duration::from_millis(20));
+    /// ```
+    ///
+    /// Game time stands still while paused, and moves at the speed of the loop:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// clock.advance(Duration::from_secs(1));
+    /// game_loop.pause();
+    /// clock.advance(Duration::from_secs(1));
+    /// game_loop.resume();
+    /// assert_eq!(game_loop.elapsed(), Duration::from_secs(1));
+    ///
+    /// game_loop.set_speed(0.5).unwrap();
+    /// clock.advance(Duration::from_secs(1));
+    /// assert_eq!(game_loop.elapsed(), Duration::from_millis(1500));
+    ///
+    /// // while 3 seconds of wall time have passed
+    /// assert_eq!(clock.now(), Duration::from_secs(3));
+    /// ```
+    pub fn elapsed(&self) -> Duration {
+        self.tick_count()
+    }
+
+    /// Takes the actions produced since the last call, each with the clock time of the iteration
+    /// that produced it, if recording was enabled with `GameLoopBuilder::record_actions`. This is
+    /// useful to dump when debugging timing issues. Requires the `std` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .record_actions(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut expected = vec![];
+    /// for frame_time in &[0, 30, 120, 10] {
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    ///     let now = clock.now();
+    ///     expected.extend(game_loop.actions().map(|action| (now, action)));
+    /// }
+    ///
+    /// assert_eq!(expected.len(), 8);
+    /// assert_eq!(game_loop.take_log(), expected);
+    /// assert!(game_loop.take_log().is_empty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn take_log(&self) -> Vec<(Duration, FrameAction)> {
+        match &self.recording {
+            Some(recording) => {
+                let mut recording = recording.lock().unwrap_or_else(PoisonError::into_inner);
+                core::mem::take(&mut *recording)
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Whether the game is currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Game time since the game started, which is frozen while paused and scaled by the speed.
+    fn tick_count(&self) -> Duration {
+        self.game_time_at(self.clock.now())
+    }
+
+    /// Game time at the given clock time.
+    fn game_time_at(&self, now: Duration) -> Duration {
+        let anchor_game_time = self.anchor_game_time.get();
+        if self.is_paused() {
+            return anchor_game_time;
+        }
+
+        // scaling goes through f64 seconds, which would lose nanoseconds over long sessions
+        let since_anchor = now.saturating_sub(self.anchor_time.get());
+        let speed = self.speed.get();
+        if speed == 1.0 {
+            anchor_game_time + since_anchor
+        } else {
+            anchor_game_time + since_anchor.mul_f64(speed)
+        }
+    }
+
+    /// The interpolation of a render at the given game time.
+    fn interpolation_at(&self, render_time: Duration) -> f64 {
+        if !self.interpolation {
+            return 1.0;
+        }
+
+        let skip_ticks = self.skip_ticks.get();
+        let next_tick = self.next_game_tick.get();
+        // another thread may have claimed a tick since the render time was read
+        let since_last_tick = (render_time + skip_ticks).saturating_sub(next_tick);
+        let interpolation = since_last_tick.as_secs_f64() / skip_ticks.as_secs_f64();
+
+        if self.clamp_interpolation && self.interpolation_mode == InterpolationMode::Interpolate {
+            interpolation.clamp(0.0, 1.0)
+        } else {
+            interpolation
+        }
+    }
+
+    /// Moves the anchor to now, so the game time can continue from its current value after a
+    /// change to the speed or pause state.
+    fn anchor(&self) {
+        let now = self.clock.now();
+        let game_time = self.game_time_at(now);
+        self.anchor_time.set(now);
+        self.anchor_game_time.set(game_time);
+    }
+
+    /// Whether a render is allowed at the given clock time under the render rate cap, noting the
+    /// time if so.
+    fn allow_render(&self, now: Duration) -> bool {
+        let min_render_interval = match self.min_render_interval {
+            Some(interval) => interval,
+            None => return true,
+        };
+
+        match self.last_render.get() {
+            // the clock may have gone backwards, in which case the cap starts over from now
+            Some(last_render) if now >= last_render && now - last_render < min_render_interval => {
+                false
+            }
+            _ => {
+                self.last_render.set(Some(now));
+                true
+            }
+        }
+    }
+
+    /// Notes an action produced in an iteration at the given clock time, if recording.
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    fn record_action(&self, now: Duration, action: FrameAction) {
+        #[cfg(feature = "std")]
+        if let Some(recording) = &self.recording {
+            recording
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((now, action));
+        }
+    }
+
+    /// Notes a render at the given clock time, for the statistics and logs.
+    fn record_render(&self, now: Duration, interpolation: f64) {
+        trace!(
+            "render with interpolation {:.3}, next tick due at {:?}",
+            interpolation,
+            self.next_game_tick.get()
+        );
+        self.stats.record_render(now);
+        #[cfg(feature = "std")]
+        self.frame_times.record_render(now);
+    }
+
+    /// With an adaptive frame skip, raises `max_frameskip` after an iteration that fell behind,
+    /// or lowers it after one that kept up, within the range.
+    fn adapt_frameskip(&self, behind: bool) {
+        if let Some((min, max)) = self.adaptive_frameskip {
+            let _ =
+                self.max_frameskip
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                        let adapted = if behind {
+                            current.saturating_add(1).min(max)
+                        } else {
+                            current.saturating_sub(1).max(min)
+                        };
+                        Some(adapted)
+                    });
+        }
+    }
+
+    /// Whether the real time since the given frame start exceeds `max_frame_time`.
+    fn budget_spent(&self, frame_start: Duration) -> bool {
+        match self.max_frame_time {
+            Some(max_frame_time) => self.clock.now().saturating_sub(frame_start) >= max_frame_time,
+            None => false,
+        }
+    }
+
+    /// If the overdue game time exceeds `max_catchup`, moves the next tick to the given game time
+    /// and returns how much game time was skipped.
+    fn jump_forward(&self, now: Duration) -> Option<Duration> {
+        let max_catchup = self.max_catchup?;
+        let overdue = now.saturating_sub(self.next_game_tick.get());
+        if overdue <= max_catchup {
+            return None;
+        }
+
+        debug!(
+            "skipping {:?} of game time, exceeding max catchup of {:?}",
+            overdue, max_catchup
+        );
+        self.next_game_tick.set(now);
+        Some(overdue)
+    }
+
+    /// Real time until either a tick is due or a render is allowed under the render rate cap.
+    /// Without a cap, this is the time until the next tick, so renders aren't spun on.
+    #[cfg(feature = "std")]
+    fn time_until_next_frame(&self) -> Duration {
+        let until_tick = self.time_until_next_tick();
+        if self.headless {
+            return until_tick;
+        }
+
+        match self.time_until_next_render() {
+            Some(until_render) => until_render.min(until_tick),
+            None => until_tick,
+        }
+    }
+
+    /// Real time until a render is allowed under the render rate cap, if there is one.
+    #[cfg(feature = "std")]
+    fn time_until_next_render(&self) -> Option<Duration> {
+        let interval = self.min_render_interval?;
+        let remaining = match self.last_render.get() {
+            Some(last_render) => {
+                let now = self.clock.now();
+                if now >= last_render {
+                    (last_render + interval).saturating_sub(now)
+                } else {
+                    Duration::from_secs(0)
+                }
+            }
+            None => Duration::from_secs(0),
+        };
+        Some(remaining)
+    }
+
+    /// Moves the next tick on by `skip_ticks`, if it is still `next_tick`. Returns false if
+    /// another thread claimed the tick first.
+    fn claim_tick(&self, next_tick: Duration, skip_ticks: Duration) -> bool {
+        let claimed = self
+            .next_game_tick
+            .compare_exchange(next_tick, next_tick + skip_ticks);
+        if claimed {
+            let total_ticks = self.total_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+            trace!(
+                "tick {} at {:?}, next tick due at {:?}",
+                total_ticks,
+                next_tick,
+                next_tick + skip_ticks
+            );
+        }
+        claimed
+    }
+}
+
+// the loops must stay shareable between threads
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<GameLoop>();
+    assert_send_sync::<GameLoop<ManualClock>>();
+    assert_send_sync::<AccumulatorLoop>();
+};
+
+/// The highest tick rate, at which there is one tick per nanosecond.
+pub(crate) const MAX_TPS: usize = 1_000_000_000;
+
+/// Whether ticks can be scheduled at the given rate per second, i.e. at least 1 and no more
+/// than `MAX_TPS`, so the interval between them is never zero.
+pub(crate) fn valid_tps(tps: usize) -> bool {
+    (1..=MAX_TPS).contains(&tps)
+}
+
+/// Time between each event at the given rate per second.
+pub(crate) fn tick_interval(per_second: usize) -> Duration {
+    Duration::from_nanos(1_000_000_000 / per_second as u64)
+}
+
+/// Events per second at the given interval, rounded to the nearest whole event. This is at least
+/// 1, so an interval longer than two seconds still reports a valid rate.
+fn rate_of(interval: Duration) -> usize {
+    ((1.0 / interval.as_secs_f64() + 0.5) as usize).max(1)
+}
+
+/// Iterator of `FrameAction`s, returned by `GameLoop::actions`.
+///
+/// At most one time jump, `max_frameskip` ticks and one render are produced, which is reflected
+/// in the upper bound of `size_hint`.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+///
+/// for frame_time in &[0, 10, 200, 1000] {
+///     clock.advance(Duration::from_millis(*frame_time));
+///
+///     let actions = game_loop.actions();
+///     let (_, upper) = actions.size_hint();
+///     assert!(actions.collect::<Vec<_>>().len() <= upper.unwrap());
+/// }
+/// ```
+///
+/// Its `Debug` output shows the progress of the iteration and the schedule it is working to:
+/// ```
+/// # use ::gameloop::*;
+/// let clock = ManualClock::new();
+/// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+///
+/// let mut actions = game_loop.actions();
+/// actions.next();
+/// assert_eq!(
+///     format!("{:?}", actions),
+///     "FrameActions { now: 0ns, skip_ticks: 50ms, next_tick: 50ms, loops: 1, rendered: false }"
+/// );
+/// ```
+pub struct FrameActions<'a, C: Clock = DefaultClock> {
+    game_loop: &'a GameLoop<C>,
+    state: FrameState,
+}
+
+/// The progress of an iteration of the game loop, kept between calls to `GameLoop::poll`.
+#[derive(Debug, Clone, Copy)]
+struct FrameState {
+    /// Clock time when the iteration started, used for all of its actions
+    now: Duration,
+
+    /// Time between each game tick when the iteration started, which ends the ticks of the
+    /// iteration if changed
+    skip_ticks: Duration,
+
+    loops: usize,
+    late_ticked: bool,
+    rendered: bool,
+    started: bool,
+    checked_init: bool,
+    checked_jump: bool,
+
+    /// Whether ticking stopped because the frame time budget was spent
+    over_budget: bool,
+
+    /// The interpolation of the render, once emitted
+    render: Option<f64>,
+}
+
+/// Iterator of `FrameAction`s, returned by `GameLoop::step_n_ticks`.
+pub struct StepActions<'a, C: Clock = DefaultClock> {
+    game_loop: &'a GameLoop<C>,
+
+    remaining: usize,
+
+    /// Whether a `FrameAction::LateTick` is still to be emitted after the ticks
+    late_tick: bool,
+
+    rendered: bool,
+}
+
+/// The actions of a whole iteration of the game loop, returned by `GameLoop::advance` and
+/// `FrameActions::outcome`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameOutcome {
+    /// The number of ticks the game should simulate, each of `GameLoop::skip_ticks`.
+    pub ticks: usize,
+
+    /// The interpolation to render the game state with after the ticks, as with
+    /// `FrameAction::Render`, or `None` if the game shouldn't render.
+    pub render: Option<f64>,
+}
+
+/// How the factor of each `FrameAction::Render` is meant to be used, set with
+/// `GameLoopBuilder::interpolation_mode`.
+///
+/// In both modes, the factor is the time since the latest tick, as a fraction of the tick
+/// duration. It is usually in `[0.0, 1.0)`, but exceeds 1.0 when the game is running behind and
+/// `max_frameskip` has been reached.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let game_loop = GameLoopBuilder::new()
+///     .tps(20)
+///     .max_frameskip(1)
+///     .interpolation_mode(InterpolationMode::Extrapolate)
+///     .clamp_interpolation(true)
+///     .clock(&clock)
+///     .build()
+///     .unwrap();
+/// game_loop.actions().for_each(drop);
+///
+/// // fall 2.5 ticks behind, so only one is run this frame
+/// clock.advance(Duration::from_millis(125));
+/// let actions: Vec<_> = game_loop.actions().collect();
+/// match actions[..] {
+///     [FrameAction::Tick { .. }, FrameAction::Render { interpolation }] => {
+///         // predict 1.5 ticks past the latest tick
+///         assert!((interpolation - 1.5).abs() < 1e-9);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterpolationMode {
+    /// Render the state between the previous tick and the latest, as
+    /// `previous + (latest - previous) * factor`. The rendered state lags up to one tick behind
+    /// the simulation, but is never a guess. A factor above 1.0 has no meaning here, so may be
+    /// clamped with `GameLoopBuilder::clamp_interpolation`. This is the default.
+    ///
+    /// This is the delayed window often used to hide jitter, e.g. for client-side prediction: the
+    /// render is always between two ticks that have already run, rather than between the latest
+    /// and one that hasn't yet. With clamping, the factor is strictly in `[0.0, 1.0]`, and stays
+    /// at 1.0, the latest tick, while the game is running behind.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .max_frameskip(2)
+    ///     .clamp_interpolation(true)
+    ///     .build()
+    ///     .unwrap();
+    /// let tick = game_loop.skip_ticks();
+    ///
+    /// for frame_time in [7, 16, 33, 5, 180, 12, 41, 3, 22, 60].iter().cycle().take(1000) {
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    ///     let render = game_loop.actions().find_map(|action| match action {
+    ///         FrameAction::Render { interpolation } => Some(interpolation),
+    ///         _ => None,
+    ///     });
+    ///     let interpolation = render.unwrap();
+    ///     assert!((0.0..=1.0).contains(&interpolation));
+    ///
+    ///     // the first tick is at 0, so the latest is at (total_ticks - 1) * tick
+    ///     let latest_tick = tick * (game_loop.total_ticks() as u32 - 1);
+    ///     if game_loop.is_behind() {
+    ///         assert_eq!(interpolation, 1.0);
+    ///     } else {
+    ///         let render_time = latest_tick + tick.mul_f64(interpolation);
+    ///         let error = render_time.as_secs_f64() - clock.now().as_secs_f64();
+    ///         assert!(error.abs() < 1e-6);
+    ///     }
+    /// }
+    /// ```
+    #[default]
+    Interpolate,
+
+    /// Predict the state past the latest tick, e.g. as `latest + velocity * factor * delta`.
+    /// The rendered state is up to date, but may briefly be wrong when the prediction is. The
+    /// factor is never clamped, so exceeds 1.0 to predict further ahead when the game is running
+    /// behind.
+    Extrapolate,
+}
 
 /// Represents a tick or render instruction, to be interpreted by your game.
 /// # Example
@@ -164,7 +2683,7 @@ pub struct FrameActions<'a> {
 ///
 ///     for action in game_loop.actions() {
 ///         match action {
-///             FrameAction::Tick => my_game.tick(),
+///             FrameAction::Tick { delta } => my_game.tick(delta.as_secs_f32()),
 ///             FrameAction::Render { interpolation } => {
 ///                 let prev_state = my_game.previous_state();
 ///                 let curr_state = my_game.current_state();
@@ -172,41 +2691,485 @@ pub struct FrameActions<'a> {
 ///                 let interpolated_state = prev_state.interpolate(curr_state, interpolation);
 ///                 my_game.render(interpolated_state);
 ///             }
+///             FrameAction::TimeJumped { skipped } => my_game.warn_skipped(skipped),
+///             FrameAction::LateTick => my_game.follow_camera(),
+///             FrameAction::Init => my_game.save_previous_state(),
+///             FrameAction::FrameStart => my_game.poll_input(),
+///             FrameAction::Ticks { count, delta } => {
+///                 my_game.tick_many(count, delta.as_secs_f32());
+///             }
+///             _ => {}
 ///         }
 ///     }
 /// }
 ///
 /// ```
-#[derive(Debug)]
+///
+/// Variants beyond `Tick` and `Render` are only emitted if enabled with the builder, and more may
+/// be added, so the enum is non-exhaustive. Match the ones the game uses and ignore the rest with
+/// a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum FrameAction {
-    /// The game should simulate one tick.
-    Tick,
+    /// The game should simulate one tick, advancing the game state by `delta`.
+    ///
+    /// This is always the fixed tick duration, i.e. `GameLoop::skip_ticks`, rather than the
+    /// measured time between ticks, so the simulation stays deterministic.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let game_loop = GameLoop::new(50, 5).unwrap();
+    ///
+    /// for action in game_loop.actions() {
+    ///     if let FrameAction::Tick { delta } = action {
+    ///         assert_eq!(delta, Duration::from_millis(20));
+    ///         assert_eq!(delta, game_loop.skip_ticks());
+    ///     }
+    /// }
+    /// ```
+    Tick { delta: Duration },
 
     /// The game should render the game state interpolated by the given amount between the previous
     /// tick and the current.
+    ///
+    /// The game time of the render is the same as that of the ticks before it, so a render at the
+    /// exact time of a tick has an interpolation of exactly 0.0. See `Interpolation` to keep the
+    /// factor to `[0.0, 1.0]` once received, and `Interpolation::get_f32` to pass it on as an
+    /// `f32`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(30, 5, &clock).unwrap();
+    ///
+    /// // one frame per tick, on the tick boundaries
+    /// for _ in 0..10_000 {
+    ///     let actions: Vec<_> = game_loop.actions().collect();
+    ///     assert!(matches!(
+    ///         actions[..],
+    ///         [
+    ///             FrameAction::Tick { .. },
+    ///             FrameAction::Render { interpolation }
+    ///         ] if interpolation == 0.0
+    ///     ));
+    ///     clock.advance(game_loop.skip_ticks());
+    /// }
+    /// ```
+    ///
+    /// When several ticks run in one iteration, the interpolation is relative to the last of them,
+    /// as that is the state the game is in when it renders:
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // the ticks at 50ms, 100ms and 150ms are due
+    /// clock.advance(Duration::from_millis(160));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// match actions[..] {
+    ///     [
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Render { interpolation }
+    ///     ] => {
+    ///         // 10ms past the tick at 150ms
+    ///         assert!((interpolation - 0.2).abs() < 1e-9);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
     Render { interpolation: f64 },
+
+    /// The game fell further behind than `GameLoopBuilder::max_catchup` allows, e.g. because the
+    /// machine was suspended, so the given amount of game time was skipped rather than caught up
+    /// on. This is only emitted if `max_catchup` is set, and is always the first action of an
+    /// iteration, other than any `FrameAction::FrameStart` or `FrameAction::Init`.
+    TimeJumped { skipped: Duration },
+
+    /// The ticks of this iteration are done, and the game should run any updates that depend on
+    /// their result, such as a camera following the player. This is only emitted if enabled with
+    /// `GameLoopBuilder::late_tick`, once after the last tick of an iteration and before the
+    /// render, and not at all if there were no ticks.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::new()
+    ///     .tps(20)
+    ///     .late_tick(true)
+    ///     .clock(&clock)
+    ///     .build()
+    ///     .unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// clock.advance(Duration::from_millis(100));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::LateTick,
+    ///         FrameAction::Render { .. }
+    ///     ]
+    /// ));
+    ///
+    /// // no ticks, so no late tick
+    /// clock.advance(Duration::from_millis(10));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(actions[..], [FrameAction::Render { .. }]));
+    /// ```
+    LateTick,
+
+    /// This is the first iteration of the game loop, and the game should capture its initial
+    /// state, e.g. as the previous state to interpolate from. This is only emitted if enabled with
+    /// `GameLoopBuilder::init`, once at the start of the first iteration, after any
+    /// `FrameAction::FrameStart`, and never again for the life of the loop.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .init(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [
+    ///         FrameAction::Init,
+    ///         FrameAction::Tick { .. },
+    ///         FrameAction::Render { .. }
+    ///     ]
+    /// ));
+    ///
+    /// for _ in 0..10 {
+    ///     clock.advance(Duration::from_millis(30));
+    ///     assert!(game_loop.actions().all(|action| action != FrameAction::Init));
+    /// }
+    /// ```
+    Init,
+
+    /// An iteration is starting, and the game should run any per-frame setup, such as polling
+    /// input. This is only emitted if enabled with `GameLoopBuilder::frame_start`, as the first
+    /// action of every iteration, even if nothing else follows it.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .frame_start(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// for frame_time in &[0, 10, 120, 30] {
+    ///     clock.advance(Duration::from_millis(*frame_time));
+    ///     let actions: Vec<_> = game_loop.actions().collect();
+    ///
+    ///     // the frame start, then any ticks, then the render
+    ///     let (first, rest) = actions.split_first().unwrap();
+    ///     let (last, ticks) = rest.split_last().unwrap();
+    ///     assert_eq!(*first, FrameAction::FrameStart);
+    ///     assert!(matches!(last, FrameAction::Render { .. }));
+    ///     assert!(ticks
+    ///         .iter()
+    ///         .all(|action| matches!(action, FrameAction::Tick { .. })));
+    /// }
+    /// ```
+    FrameStart,
+
+    /// The game should simulate `count` ticks at once, each advancing the game state by `delta`,
+    /// e.g. as a single larger physics step when catching up. This is only emitted if enabled
+    /// with `GameLoopBuilder::coalesce_ticks`, in place of the `FrameAction::Tick`s of an
+    /// iteration, and not at all if there were no ticks. The ticks are scheduled exactly as they
+    /// would be otherwise. Lockstep loops only ever tick once, so always emit `FrameAction::Tick`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoopBuilder::with_clock(&clock)
+    ///     .tps(20)
+    ///     .coalesce_ticks(true)
+    ///     .build()
+    ///     .unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// clock.advance(Duration::from_millis(160));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(
+    ///     actions[..],
+    ///     [
+    ///         FrameAction::Ticks { count: 3, delta },
+    ///         FrameAction::Render { .. }
+    ///     ] if delta == Duration::from_millis(50)
+    /// ));
+    /// assert_eq!(game_loop.total_ticks(), 4);
+    ///
+    /// // still limited by max_frameskip
+    /// clock.advance(Duration::from_secs(1));
+    /// let actions: Vec<_> = game_loop.actions().collect();
+    /// assert!(matches!(actions[0], FrameAction::Ticks { count: 5, .. }));
+    /// ```
+    Ticks { count: usize, delta: Duration },
 }
 
-impl<'a> Iterator for FrameActions<'a> {
-    type Item = FrameAction;
+impl FrameState {
+    fn new(now: Duration, skip_ticks: Duration) -> Self {
+        Self {
+            now,
+            skip_ticks,
+            loops: 0,
+            late_ticked: false,
+            rendered: false,
+            started: false,
+            checked_init: false,
+            checked_jump: false,
+            over_budget: false,
+            render: None,
+        }
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let next_tick = self.game_loop.next_game_tick.get();
+impl<'a, C: Clock> FrameActions<'a, C> {
+    /// The ticks and render emitted by this iteration so far, e.g. for metrics once it has been
+    /// drained. The ticks of a `FrameAction::Ticks` are each counted.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    /// game_loop.actions().for_each(drop);
+    ///
+    /// // stall for 3 ticks
+    /// clock.advance(Duration::from_millis(160));
+    /// let mut actions = game_loop.actions();
+    /// for _action in &mut actions {
+    ///     // ...
+    /// }
+    ///
+    /// let outcome = actions.outcome();
+    /// assert_eq!(outcome.ticks, 3);
+    /// assert!((outcome.render.unwrap() - 0.2).abs() < 1e-9);
+    /// ```
+    pub fn outcome(&self) -> FrameOutcome {
+        FrameOutcome {
+            ticks: self.state.loops,
+            render: self.state.render,
+        }
+    }
+
+    /// The number of ticks still to be emitted by this iteration, limited by `max_frameskip`,
+    /// without consuming them.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    ///
+    /// clock.advance(Duration::from_millis(120));
+    /// let mut actions = game_loop.actions();
+    /// assert_eq!(actions.pending_ticks(), 3);
+    ///
+    /// assert!(matches!(actions.next(), Some(FrameAction::Tick { .. })));
+    /// assert_eq!(actions.pending_ticks(), 2);
+    /// let is_tick = |action: &FrameAction| matches!(action, FrameAction::Tick { .. });
+    /// assert_eq!(actions.filter(is_tick).count(), 2);
+    ///
+    /// // limited by max_frameskip
+    /// clock.advance(Duration::from_secs(1));
+    /// assert_eq!(game_loop.actions().pending_ticks(), 5);
+    /// ```
+    pub fn pending_ticks(&self) -> usize {
+        let game_loop = self.game_loop;
+        if game_loop.is_paused() {
+            return 0;
+        }
 
-        if self.game_loop.tick_count() > next_tick && self.loops < self.game_loop.max_frameskip {
-            self.game_loop.increment_next_game_tick();
-            self.loops += 1;
-            return Some(FrameAction::Tick);
+        if game_loop.lockstep {
+            return 1 - self.state.loops;
         }
 
-        if !self.rendered {
-            self.rendered = true;
+        let now = game_loop.game_time_at(self.state.now);
+        let next_tick = game_loop.next_game_tick.get();
+        if now < next_tick {
+            return 0;
+        }
+
+        let overdue = now - next_tick;
+        let jumping = !self.state.checked_jump
+            && matches!(game_loop.max_catchup, Some(max_catchup) if overdue > max_catchup);
+        let due = if jumping {
+            1
+        } else {
+            overdue.as_nanos() / game_loop.skip_ticks.get().as_nanos() + 1
+        };
 
-            let render_time = self.game_loop.tick_count();
-            let skip_ticks = self.game_loop.skip_ticks;
-            let interpolation: f64 =
-                ((render_time + skip_ticks - next_tick) as f64) / (skip_ticks as f64);
+        let remaining = game_loop.max_frameskip().saturating_sub(self.state.loops);
+        due.min(remaining as u128) as usize
+    }
+
+    /// `FrameAction::LateTick`, if enabled and this is the end of the ticks of the iteration.
+    fn late_tick(&mut self) -> Option<FrameAction> {
+        if !self.game_loop.late_tick || self.state.late_ticked || self.state.loops == 0 {
+            return None;
+        }
+
+        self.state.late_ticked = true;
+        Some(FrameAction::LateTick)
+    }
+
+    /// The next action in lockstep, which is a single tick then a render at exactly that tick.
+    fn next_lockstep(&mut self) -> Option<FrameAction> {
+        let paused = self.game_loop.is_paused();
+        while !paused && self.state.loops == 0 {
+            let next_tick = self.game_loop.next_game_tick.get();
+            let delta = self.state.skip_ticks;
+            if self.game_loop.claim_tick(next_tick, delta) {
+                self.game_loop.stats.record_tick(self.state.now);
+                self.state.loops += 1;
+                return Some(FrameAction::Tick { delta });
+            }
+        }
+
+        if let Some(late_tick) = self.late_tick() {
+            return Some(late_tick);
+        }
+
+        if !self.state.rendered {
+            self.state.rendered = true;
+            if self.game_loop.headless || (paused && !self.game_loop.render_while_paused) {
+                return None;
+            }
+
+            self.game_loop.record_render(self.state.now, 1.0);
+            self.state.render = Some(1.0);
+            return Some(FrameAction::Render { interpolation: 1.0 });
+        }
+
+        None
+    }
+
+    /// The next action of the iteration, before it is recorded.
+    fn next_action(&mut self) -> Option<FrameAction> {
+        if !self.state.started {
+            self.state.started = true;
+            if self.game_loop.frame_start {
+                return Some(FrameAction::FrameStart);
+            }
+        }
+
+        if !self.state.checked_init {
+            self.state.checked_init = true;
+            if self.game_loop.init && !self.game_loop.initialized.swap(true, Ordering::Relaxed) {
+                return Some(FrameAction::Init);
+            }
+        }
+
+        if self.game_loop.lockstep {
+            return self.next_lockstep();
+        }
+
+        let game_time = self.game_loop.game_time_at(self.state.now);
+
+        if !self.state.checked_jump {
+            self.state.checked_jump = true;
+            if let Some(skipped) = self.game_loop.jump_forward(game_time) {
+                return Some(FrameAction::TimeJumped { skipped });
+            }
+        }
+
+        let paused = self.game_loop.is_paused();
+        let mut coalesced = 0;
+        let next_tick = loop {
+            let next_tick = self.game_loop.next_game_tick.get();
+            if paused
+                || self.state.late_ticked
+                || self.game_loop.skip_ticks.get() != self.state.skip_ticks
+                || game_time < next_tick
+                || self.state.loops >= self.game_loop.max_frameskip()
+            {
+                break next_tick;
+            }
+
+            if self.state.loops > 0 && self.game_loop.budget_spent(self.state.now) {
+                self.state.over_budget = true;
+                break next_tick;
+            }
+
+            // if another thread claimed this tick first, try again with the one after
+            let delta = self.state.skip_ticks;
+            if self.game_loop.claim_tick(next_tick, delta) {
+                self.game_loop.stats.record_tick(self.state.now);
+                self.state.loops += 1;
+                if self.game_loop.coalesce_ticks {
+                    coalesced += 1;
+                    continue;
+                }
+                return Some(FrameAction::Tick { delta });
+            }
+        };
+
+        if coalesced > 0 {
+            return Some(FrameAction::Ticks {
+                count: coalesced,
+                delta: self.state.skip_ticks,
+            });
+        }
+
+        if let Some(late_tick) = self.late_tick() {
+            return Some(late_tick);
+        }
+
+        if !self.state.rendered {
+            self.state.rendered = true;
 
+            // still more ticks due after max_frameskip or the budget, the game is falling behind
+            let capped =
+                self.state.loops >= self.game_loop.max_frameskip() || self.state.over_budget;
+            let behind = !paused && capped && game_time >= next_tick;
+            self.game_loop.behind.store(behind, Ordering::Relaxed);
+            self.game_loop.adapt_frameskip(behind);
+            if behind {
+                self.game_loop
+                    .stats
+                    .record_frameskip_saturated(self.state.now);
+            }
+
+            let render =
+                !self.game_loop.headless && (!paused || self.game_loop.render_while_paused);
+            let throttled = render && !self.game_loop.allow_render(self.state.now);
+            self.game_loop
+                .render_throttled
+                .store(throttled, Ordering::Relaxed);
+            if !render || throttled {
+                return None;
+            }
+
+            let interpolation = self.game_loop.interpolation_at(game_time);
+            self.game_loop.record_render(self.state.now, interpolation);
+            self.state.render = Some(interpolation);
             return Some(FrameAction::Render { interpolation });
         }
 
@@ -214,13 +3177,144 @@ impl<'a> Iterator for FrameActions<'a> {
     }
 }
 
+impl<'a, C: Clock> Iterator for FrameActions<'a, C> {
+    type Item = FrameAction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let action = self.next_action()?;
+        self.game_loop.record_action(self.state.now, action);
+        Some(action)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let max_ticks = if self.game_loop.lockstep {
+            1
+        } else {
+            self.game_loop.max_frameskip()
+        };
+        let ticks = max_ticks.saturating_sub(self.state.loops);
+        let render = if self.state.rendered || self.game_loop.headless {
+            0
+        } else {
+            1
+        };
+        let jump = if self.state.checked_jump
+            || self.game_loop.lockstep
+            || self.game_loop.max_catchup.is_none()
+        {
+            0
+        } else {
+            1
+        };
+        let late_tick = (self.game_loop.late_tick && !self.state.late_ticked) as usize;
+        let init = (self.game_loop.init && !self.state.checked_init) as usize;
+        let frame_start = (self.game_loop.frame_start && !self.state.started) as usize;
+        (
+            0,
+            ticks.checked_add(render + jump + late_tick + init + frame_start),
+        )
+    }
+}
+
+impl<'a, C: Clock> Debug for FrameActions<'a, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FrameActions")
+            .field("now", &self.state.now)
+            .field("skip_ticks", &self.state.skip_ticks)
+            .field("next_tick", &self.game_loop.next_game_tick.get())
+            .field("loops", &self.state.loops)
+            .field("rendered", &self.state.rendered)
+            .finish()
+    }
+}
+
+impl<'a, C: Clock> StepActions<'a, C> {
+    /// The next action at the given clock time, before it is recorded.
+    fn next_action(&mut self, now: Duration) -> Option<FrameAction> {
+        while self.remaining > 0 {
+            let next_tick = self.game_loop.next_game_tick.get();
+            let delta = self.game_loop.skip_ticks.get();
+            if self.game_loop.claim_tick(next_tick, delta) {
+                self.game_loop.stats.record_tick(now);
+                self.remaining -= 1;
+                return Some(FrameAction::Tick { delta });
+            }
+        }
+
+        if self.late_tick {
+            self.late_tick = false;
+            return Some(FrameAction::LateTick);
+        }
+
+        if !self.rendered && !self.game_loop.headless {
+            self.rendered = true;
+            self.game_loop.record_render(now, 1.0);
+            return Some(FrameAction::Render { interpolation: 1.0 });
+        }
+
+        None
+    }
+}
+
+impl<'a, C: Clock> Iterator for StepActions<'a, C> {
+    type Item = FrameAction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let now = self.game_loop.clock.now();
+        let action = self.next_action(now)?;
+        self.game_loop.record_action(now, action);
+        Some(action)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let render = if self.rendered || self.game_loop.headless {
+            0
+        } else {
+            1
+        };
+        let others = render + self.late_tick as usize;
+        (
+            self.remaining.saturating_add(others),
+            self.remaining.checked_add(others),
+        )
+    }
+}
+
 impl Display for GameLoopError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
-            GameLoopError::BadTps => write!(f, "Ticks per second must be >= 1"),
-            GameLoopError::BadFrameSkip => write!(f, "Max frame skip must be >= 1"),
+            GameLoopError::BadTps(tps) => {
+                write!(
+                    f,
+                    "ticks per second must be between 1 and {}, got {}",
+                    MAX_TPS, tps
+                )
+            }
+            GameLoopError::BadTickDuration(tick) => {
+                write!(f, "tick duration must be non-zero, got {:?}", tick)
+            }
+            GameLoopError::BadFrameSkip(max_frameskip) => {
+                write!(f, "max frame skip must be >= 1, got {}", max_frameskip)
+            }
+            GameLoopError::BadFrameSkipRange(min, max) => {
+                write!(
+                    f,
+                    "frame skip range must have 1 <= min <= max, got {}..={}",
+                    min, max
+                )
+            }
+            GameLoopError::BadMaxFps(max_fps) => {
+                write!(f, "max frames per second must be >= 1, got {}", max_fps)
+            }
+            GameLoopError::BadSpeed(speed) => {
+                write!(f, "speed must be finite and >= 0, got {}", speed)
+            }
+            GameLoopError::BadFrameTimeWindow(window) => {
+                write!(f, "frame time window must be >= 1, got {}", window)
+            }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for GameLoopError {}
@@ -0,0 +1,84 @@
+/// An interpolation factor in `[0.0, 1.0]`, for blending between the previous and latest tick.
+///
+/// The factor of `FrameAction::Render` is a bare `f64`, as it exceeds 1.0 when the game is running
+/// behind or with `InterpolationMode::Extrapolate`. When interpolating, wrapping it in this makes
+/// the range explicit, so it can be passed around without being clamped again.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// // out of range factors are clamped
+/// assert_eq!(Interpolation::new(0.25).get(), 0.25);
+/// assert_eq!(Interpolation::new(1.5).get(), 1.0);
+/// assert_eq!(Interpolation::new(-0.5).get(), 0.0);
+/// assert_eq!(Interpolation::new(f64::NAN).get(), 0.0);
+///
+/// let interpolation = Interpolation::new(0.25);
+/// assert_eq!(interpolation.lerp(10.0, 20.0), 12.5);
+/// assert_eq!(Interpolation::new(2.0).lerp(10.0, 20.0), 20.0);
+///
+/// // ready for a shader uniform
+/// let uniform: f32 = interpolation.into();
+/// assert_eq!(uniform, 0.25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Interpolation(f64);
+
+impl Interpolation {
+    /// Wraps the given factor, clamped to `[0.0, 1.0]`. NaN is treated as 0.0.
+    pub fn new(factor: f64) -> Self {
+        if factor.is_nan() {
+            Self(0.0)
+        } else {
+            Self(factor.clamp(0.0, 1.0))
+        }
+    }
+
+    /// The factor, which is always in `[0.0, 1.0]`.
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    /// The factor as an `f32`, such as for a shader uniform. It is computed as an `f64` for
+    /// precision, and only narrowed here.
+    pub fn get_f32(self) -> f32 {
+        self.0 as f32
+    }
+
+    /// Blends between `previous` at 0.0 and `latest` at 1.0. See `lerp`.
+    pub fn lerp(self, previous: f64, latest: f64) -> f64 {
+        lerp(previous, latest, self.0)
+    }
+}
+
+/// Blends between the state of the `previous` tick at 0.0 and the `latest` tick at 1.0, by the
+/// interpolation `t` of a `FrameAction::Render`. The factor is the time since the latest tick, so
+/// `previous` must be the older state; swapping them renders the motion backwards.
+///
+/// `t` is not clamped, so this extrapolates past `latest` above 1.0, as with
+/// `InterpolationMode::Extrapolate`. Use `Interpolation::lerp` to keep within the two.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// let (previous, latest) = (10.0, 20.0);
+/// assert_eq!(lerp(previous, latest, 0.0), previous);
+/// assert_eq!(lerp(previous, latest, 0.25), 12.5);
+/// assert_eq!(lerp(previous, latest, 1.0), latest);
+/// assert_eq!(lerp(previous, latest, 1.5), 25.0);
+/// ```
+pub fn lerp(previous: f64, latest: f64, t: f64) -> f64 {
+    previous + (latest - previous) * t
+}
+
+impl From<Interpolation> for f64 {
+    fn from(interpolation: Interpolation) -> Self {
+        interpolation.get()
+    }
+}
+
+impl From<Interpolation> for f32 {
+    fn from(interpolation: Interpolation) -> Self {
+        interpolation.get_f32()
+    }
+}
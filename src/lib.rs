@@ -8,23 +8,108 @@
 //! // run at 20 ticks per second, with max frame skip of 5
 //! let game_loop = GameLoop::new(20, 5).unwrap();
 //!
+//! // ... load assets, open the window ...
+//!
+//! // don't catch up on the time spent loading
+//! game_loop.start();
+//!
 //! // begin core game loop
 //! loop {
 //!     // ... handle window events ...
 //!
 //!     for action in game_loop.actions() {
 //!         match action {
-//!             FrameAction::Tick => /* simulate 1 game tick */
+//!             FrameAction::Tick { .. } => /* simulate 1 game tick */
 //! # {},
 //!             FrameAction::Render { interpolation } => /* render the game state interpolated
 //!                                                         between previous and next tick */
 //! # {},
+//!             FrameAction::TimeJumped { .. } => /* the game time jumped forward */
+//! # {},
+//!             FrameAction::LateTick => /* after the ticks, if enabled */
+//! # {},
+//!             FrameAction::Init => /* before the first tick, if enabled */
+//! # {},
+//!             FrameAction::FrameStart => /* before anything else, if enabled */
+//! # {},
+//!             FrameAction::Ticks { count, .. } => /* simulate `count` ticks at once, if enabled */
+//! # {},
+//!             _ => /* variants added in later versions */
+//! # {},
 //!         }
 //!     }
 //! # break; // not infinite pls
 //! }
 //! ```
+//!
+//! # Configuration
+//!
+//! Options beyond the tick rate and max frame skip are set with a `GameLoopBuilder`:
+//!
+//! ```
+//! # use ::gameloop::*;
+//! let game_loop = GameLoop::builder()
+//!     .tps(60)
+//!     .max_frameskip(5)
+//!     .clamp_interpolation(true)
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! # Features
+//!
+//! * `std` (default): provides `SystemClock`, `MultiRateLoop` and the real time constructors
+//!   such as `GameLoop::new`. Without it, the crate is `no_std` and a `Clock` must be supplied with
+//!   `GameLoop::with_clock` or `GameLoopBuilder::with_clock`. The loop's state is kept in
+//!   [`portable-atomic`](https://docs.rs/portable-atomic) types, so targets without 64-bit
+//!   atomics, such as `thumbv7em-none-eabihf`, are supported. Targets without atomic
+//!   compare-and-swap, such as `thumbv6m-none-eabi`, additionally need one of `portable-atomic`'s
+//!   options for them, e.g. its `critical-section` feature.
+//! * `log` (default): logs changes to the loop, such as pausing or changing the tick rate, at the
+//!   debug level through the [`log`](https://docs.rs/log) facade. Each tick and render is logged
+//!   at the trace level, which can be compiled out with `log`'s `release_max_level_*` features.
+//! * `tracing`: logs the same events through [`tracing`](https://docs.rs/tracing) instead, with
+//!   structured fields such as `tps` and `skip_ticks` where available. Both facades can be
+//!   enabled at once, or neither to disable logging entirely.
+//! * `serde`: implements `Serialize` and `Deserialize` for `GameLoopConfig`, to save and restore
+//!   a loop.
+//! * `wasm`: provides `WasmClock`, backed by the browser's `performance.now()`. `Instant` panics
+//!   on `wasm32-unknown-unknown`, so the real time constructors can't be used there; pass a
+//!   `WasmClock` to `GameLoop::with_clock` instead. See `examples/wasm.rs`.
+//! * `async`: provides `GameLoop::wait_next_tick`, which sleeps on the
+//!   [`tokio`](https://docs.rs/tokio) timer rather than blocking the thread.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+mod macros;
 
+mod accumulator;
+mod atomic;
+mod builder;
+mod clock;
+mod config;
 mod gameloop;
+mod interpolation;
+#[cfg(feature = "std")]
+mod multirate;
+mod stats;
 
-pub use self::gameloop::{FrameAction, FrameActions, GameLoop, GameLoopError};
+pub use self::accumulator::{AccumulatorActions, AccumulatorLoop};
+pub use self::builder::GameLoopBuilder;
+#[cfg(not(feature = "std"))]
+pub use self::clock::NoClock;
+#[cfg(feature = "wasm")]
+pub use self::clock::WasmClock;
+pub use self::clock::{Clock, DefaultClock, ManualClock};
+#[cfg(feature = "std")]
+pub use self::clock::{ScriptedClock, SystemClock};
+pub use self::config::GameLoopConfig;
+pub use self::gameloop::{
+    FrameAction, FrameActions, FrameOutcome, GameLoop, GameLoopError, InterpolationMode,
+    StepActions,
+};
+pub use self::interpolation::{lerp, Interpolation};
+#[cfg(feature = "std")]
+pub use self::multirate::{MultiRateAction, MultiRateActions, MultiRateLoop};
+pub use self::stats::LoopStats;
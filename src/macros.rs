@@ -0,0 +1,25 @@
+/// Logs a debug message through whichever of the `log` and `tracing` features are enabled, or
+/// nowhere if neither is.
+macro_rules! debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)+);
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)+);
+        #[cfg(not(any(feature = "log", feature = "tracing")))]
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+/// Logs a trace message in the same way as `debug!`. Both facades can compile these out entirely
+/// at release log levels, through their `release_max_level_*` features.
+macro_rules! trace {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        log::trace!($($arg)+);
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)+);
+        #[cfg(not(any(feature = "log", feature = "tracing")))]
+        let _ = format_args!($($arg)+);
+    }};
+}
@@ -0,0 +1,238 @@
+use core::time::Duration;
+
+use crate::atomic::AtomicDuration;
+use crate::clock::{Clock, DefaultClock, SystemClock};
+use crate::gameloop::{tick_interval, valid_tps, GameLoopError};
+
+/// A game loop with several independent tick rates, called channels, driven by a single clock so
+/// they never drift apart. Requires the `std` feature.
+///
+/// Each iteration of `actions()` emits the due ticks of every channel in the order they were
+/// scheduled, then a single render. `max_frameskip` applies to each channel separately.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let game_loop = MultiRateLoop::with_clock(5, &clock)
+///     .unwrap()
+///     .channel("physics", 120)
+///     .unwrap()
+///     .channel("ai", 10)
+///     .unwrap();
+///
+/// let mut physics_ticks = 0;
+/// let mut ai_ticks = 0;
+/// let mut renders = 0;
+/// for _ in 0..100 {
+///     for action in game_loop.actions() {
+///         match action {
+///             MultiRateAction::Tick { channel: "physics" } => physics_ticks += 1,
+///             MultiRateAction::Tick { channel: "ai" } => ai_ticks += 1,
+///             MultiRateAction::Tick { .. } => unreachable!(),
+///             MultiRateAction::Render => {
+///                 let _physics = game_loop.interpolation("physics").unwrap();
+///                 renders += 1;
+///             }
+///         }
+///     }
+///
+///     clock.advance(Duration::from_millis(10));
+/// }
+///
+/// assert_eq!(physics_ticks, 119);
+/// assert_eq!(ai_ticks, 10);
+/// assert_eq!(renders, 100);
+/// ```
+pub struct MultiRateLoop<C: Clock = DefaultClock> {
+    /// The source of time
+    clock: C,
+
+    /// Clock time when the loop was created
+    start_time: Duration,
+
+    /// Maximum number of consecutive ticks of each channel before a render is mandatory
+    max_frameskip: usize,
+
+    channels: Vec<Channel>,
+}
+
+struct Channel {
+    name: &'static str,
+
+    /// Time between each tick of this channel
+    skip_ticks: Duration,
+
+    /// Time since the game started for the next scheduled tick of this channel
+    next_tick: AtomicDuration,
+}
+
+/// Represents a tick or render instruction from a `MultiRateLoop`.
+///
+/// This is separate from `FrameAction`, as a `GameLoop` has no channels. A channel on
+/// `FrameAction::Tick` would be the same for every tick of a `GameLoop`, but would still have to
+/// be matched on by every game using one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MultiRateAction {
+    /// The game should simulate one tick of the named channel.
+    Tick { channel: &'static str },
+
+    /// The game should render. The interpolation of each channel is given by
+    /// `MultiRateLoop::interpolation`.
+    Render,
+}
+
+/// Iterator of `MultiRateAction`s, returned by `MultiRateLoop::actions`.
+pub struct MultiRateActions<'a, C: Clock = DefaultClock> {
+    game_loop: &'a MultiRateLoop<C>,
+
+    /// Game time when the iteration started, used for all of its actions
+    now: Duration,
+
+    /// Number of ticks of each channel emitted by this iteration, in the order of the channels
+    loops: Vec<usize>,
+
+    rendered: bool,
+}
+
+impl MultiRateLoop {
+    /// Create a new loop with no channels that runs in real time. See `GameLoop::new` for the
+    /// meaning of `max_frameskip`, which must be >= 1.
+    pub fn new(max_frameskip: usize) -> Result<Self, GameLoopError> {
+        Self::with_clock(max_frameskip, SystemClock::new())
+    }
+}
+
+impl<C: Clock> MultiRateLoop<C> {
+    /// Create a new loop with no channels, driven by the given clock. See `new` for the meaning
+    /// of the other arguments.
+    pub fn with_clock(max_frameskip: usize, clock: C) -> Result<Self, GameLoopError> {
+        if max_frameskip == 0 {
+            return Err(GameLoopError::BadFrameSkip(max_frameskip));
+        }
+
+        let start_time = clock.now();
+        Ok(Self {
+            clock,
+            start_time,
+            max_frameskip,
+            channels: Vec::new(),
+        })
+    }
+
+    /// Adds a channel that ticks `tps` times per second, which must be >= 1 and at most one per
+    /// nanosecond. Its first tick is due immediately.
+    pub fn channel(mut self, name: &'static str, tps: usize) -> Result<Self, GameLoopError> {
+        if !valid_tps(tps) {
+            return Err(GameLoopError::BadTps(tps));
+        }
+
+        let next_tick = self.game_time();
+        self.channels.push(Channel {
+            name,
+            skip_ticks: tick_interval(tps),
+            next_tick: AtomicDuration::new(next_tick),
+        });
+        Ok(self)
+    }
+
+    /// Should be called once per iteration of your core game loop. See `GameLoop::actions`.
+    ///
+    /// Each iteration counts its own ticks towards `max_frameskip`, and each tick is only ever
+    /// emitted once, even if several iterations are drained at the same time.
+    ///
+    /// # Example
+    /// ```
+    /// # use ::gameloop::*;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let game_loop = MultiRateLoop::with_clock(2, &clock)
+    ///     .unwrap()
+    ///     .channel("physics", 20)
+    ///     .unwrap();
+    ///
+    /// // the ticks at 0ms, 50ms, 100ms and 150ms are due
+    /// clock.advance(Duration::from_millis(150));
+    /// let mut first = game_loop.actions();
+    /// let mut second = game_loop.actions();
+    /// let physics = MultiRateAction::Tick { channel: "physics" };
+    /// assert_eq!(first.next(), Some(physics));
+    /// assert_eq!(second.next(), Some(physics));
+    /// assert_eq!(second.next(), Some(physics));
+    ///
+    /// // the second iteration reached max_frameskip, but the first has one tick left
+    /// assert_eq!(second.next(), Some(MultiRateAction::Render));
+    /// assert_eq!(first.next(), Some(physics));
+    /// assert_eq!(first.next(), Some(MultiRateAction::Render));
+    /// ```
+    pub fn actions(&self) -> MultiRateActions<'_, C> {
+        MultiRateActions {
+            game_loop: self,
+            now: self.game_time(),
+            loops: vec![0; self.channels.len()],
+            rendered: false,
+        }
+    }
+
+    /// The interpolation between the previous and next tick of the named channel, as with
+    /// `FrameAction::Render`, or `None` if there is no such channel.
+    pub fn interpolation(&self, channel: &str) -> Option<f64> {
+        let channel = self.channels.iter().find(|c| c.name == channel)?;
+        let render_time = self.game_time();
+        let skip_ticks = channel.skip_ticks;
+        let interpolation = (render_time + skip_ticks).saturating_sub(channel.next_tick.get());
+        Some(interpolation.as_secs_f64() / skip_ticks.as_secs_f64())
+    }
+
+    /// Time since the loop was created.
+    fn game_time(&self) -> Duration {
+        self.clock.now().saturating_sub(self.start_time)
+    }
+}
+
+impl<'a, C: Clock> Iterator for MultiRateActions<'a, C> {
+    type Item = MultiRateAction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let now = self.now;
+        let max_frameskip = self.game_loop.max_frameskip;
+
+        loop {
+            // the earliest due tick goes first, ties going to the first registered
+            let due = self
+                .game_loop
+                .channels
+                .iter()
+                .zip(&self.loops)
+                .enumerate()
+                .filter(|(_, (_, loops))| **loops < max_frameskip)
+                .map(|(index, (channel, _))| (index, channel, channel.next_tick.get()))
+                .filter(|(_, _, next_tick)| *next_tick <= now)
+                .min_by_key(|(_, _, next_tick)| *next_tick);
+
+            let (index, channel, next_tick) = match due {
+                Some(due) => due,
+                None => break,
+            };
+
+            // if another thread claimed this tick first, look for the next one due
+            if channel
+                .next_tick
+                .compare_exchange(next_tick, next_tick + channel.skip_ticks)
+            {
+                self.loops[index] += 1;
+                return Some(MultiRateAction::Tick {
+                    channel: channel.name,
+                });
+            }
+        }
+
+        if !self.rendered {
+            self.rendered = true;
+            return Some(MultiRateAction::Render);
+        }
+
+        None
+    }
+}
@@ -0,0 +1,226 @@
+use core::time::Duration;
+#[cfg(feature = "std")]
+use portable_atomic::AtomicUsize;
+use portable_atomic::{AtomicU32, Ordering};
+
+use crate::atomic::AtomicDuration;
+#[cfg(feature = "std")]
+use crate::atomic::AtomicOptionDuration;
+
+/// Length of the window that statistics are measured over.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Measured performance of a `GameLoop`, returned by `GameLoop::stats`.
+///
+/// Each value is an estimate over the last second of real time, so it reacts to current
+/// conditions rather than averaging since the game started. Counts are kept for the current and
+/// previous one second windows, and the previous window is weighted by how much of it still
+/// overlaps the last second. During the first second, the values ramp up from zero.
+///
+/// # Example
+/// ```
+/// # use ::gameloop::*;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+///
+/// // render every 10ms for two seconds
+/// for _ in 0..200 {
+///     clock.advance(Duration::from_millis(10));
+///     game_loop.actions().for_each(drop);
+/// }
+///
+/// let stats = game_loop.stats();
+/// assert!((stats.fps - 100.0).abs() <= 1.0);
+/// assert!((stats.tps - 20.0).abs() <= 1.0);
+/// assert_eq!(stats.frameskip_saturated, 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LoopStats {
+    /// Renders per second.
+    pub fps: f32,
+
+    /// Ticks per second, which falls below the configured rate when the game is running behind.
+    pub tps: f32,
+
+    /// Number of iterations in the last second that hit `max_frameskip`, meaning the game fell
+    /// behind and slowed down.
+    pub frameskip_saturated: u32,
+}
+
+/// Counts events over a sliding window, in constant memory.
+#[derive(Debug)]
+pub(crate) struct StatsTracker {
+    /// Clock time the current window started
+    window_start: AtomicDuration,
+
+    current: Counts,
+    previous: Counts,
+}
+
+/// The intervals between the most recent renders, for a smoothed frame time. Requires the `std`
+/// feature.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub(crate) struct FrameTimes {
+    /// Clock time of the last render
+    last_render: AtomicOptionDuration,
+
+    /// Ring buffer of intervals, filled from the start
+    samples: Box<[AtomicDuration]>,
+
+    /// Index of the next sample to write, modulo the number of samples
+    next: AtomicUsize,
+
+    /// Number of samples written, up to the number of samples
+    len: AtomicUsize,
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    renders: AtomicU32,
+    ticks: AtomicU32,
+    frameskip_saturated: AtomicU32,
+}
+
+impl StatsTracker {
+    pub(crate) fn new(now: Duration) -> Self {
+        Self {
+            window_start: AtomicDuration::new(now),
+            current: Counts::default(),
+            previous: Counts::default(),
+        }
+    }
+
+    pub(crate) fn reset(&self, now: Duration) {
+        self.window_start.set(now);
+        self.current.clear();
+        self.previous.clear();
+    }
+
+    pub(crate) fn record_tick(&self, now: Duration) {
+        self.roll(now);
+        self.current.ticks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_render(&self, now: Duration) {
+        self.roll(now);
+        self.current.renders.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_frameskip_saturated(&self, now: Duration) {
+        self.roll(now);
+        self.current
+            .frameskip_saturated
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self, now: Duration) -> LoopStats {
+        self.roll(now);
+
+        let window_progress =
+            now.saturating_sub(self.window_start.get()).as_secs_f32() / WINDOW.as_secs_f32();
+        let previous_weight = 1.0 - window_progress;
+
+        let estimate = |previous: &AtomicU32, current: &AtomicU32| {
+            previous.load(Ordering::Relaxed) as f32 * previous_weight
+                + current.load(Ordering::Relaxed) as f32
+        };
+        LoopStats {
+            fps: estimate(&self.previous.renders, &self.current.renders),
+            tps: estimate(&self.previous.ticks, &self.current.ticks),
+            frameskip_saturated: (estimate(
+                &self.previous.frameskip_saturated,
+                &self.current.frameskip_saturated,
+            ) + 0.5) as u32,
+        }
+    }
+
+    /// Moves the window forward so that it contains `now`.
+    fn roll(&self, now: Duration) {
+        let window_start = self.window_start.get();
+        let windows_passed =
+            (now.saturating_sub(window_start).as_nanos() / WINDOW.as_nanos()) as u32;
+
+        match windows_passed {
+            0 => return,
+            1 => self.previous.take_from(&self.current),
+            _ => {
+                self.previous.clear();
+                self.current.clear();
+            }
+        }
+
+        self.window_start
+            .set(window_start + WINDOW * windows_passed);
+    }
+}
+
+#[cfg(feature = "std")]
+impl FrameTimes {
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            last_render: AtomicOptionDuration::new(None),
+            samples: (0..window).map(|_| AtomicDuration::default()).collect(),
+            next: AtomicUsize::new(0),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        self.last_render.set(None);
+        self.next.store(0, Ordering::Relaxed);
+        self.len.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_render(&self, now: Duration) {
+        let last_render = match self.last_render.swap(Some(now)) {
+            Some(last_render) => last_render,
+            None => return,
+        };
+
+        let window = self.samples.len();
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % window;
+        self.samples[index].set(now.saturating_sub(last_render));
+        let _ = self
+            .len
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |len| {
+                if len < window {
+                    Some(len + 1)
+                } else {
+                    None
+                }
+            });
+    }
+
+    /// The mean of the recorded intervals, or zero if there are none yet.
+    pub(crate) fn average(&self) -> Duration {
+        let len = self.len.load(Ordering::Relaxed);
+        if len == 0 {
+            return Duration::from_secs(0);
+        }
+
+        let total: u128 = self.samples[..len]
+            .iter()
+            .map(|sample| sample.get().as_nanos())
+            .sum();
+        Duration::from_nanos((total / len as u128) as u64)
+    }
+}
+
+impl Counts {
+    fn clear(&self) {
+        self.renders.store(0, Ordering::Relaxed);
+        self.ticks.store(0, Ordering::Relaxed);
+        self.frameskip_saturated.store(0, Ordering::Relaxed);
+    }
+
+    /// Moves the counts from `other` into `self`, leaving `other` cleared.
+    fn take_from(&self, other: &Counts) {
+        let take = |counter: &AtomicU32| counter.swap(0, Ordering::Relaxed);
+        self.renders.store(take(&other.renders), Ordering::Relaxed);
+        self.ticks.store(take(&other.ticks), Ordering::Relaxed);
+        self.frameskip_saturated
+            .store(take(&other.frameskip_saturated), Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,49 @@
+//! Checks of the conversions between tick rates and tick intervals.
+
+use gameloop::*;
+use std::time::Duration;
+
+#[test]
+fn tps_round_trips_through_interval() {
+    // the truncation of the interval is undone by the rounding
+    for tps in 1..=20_000 {
+        let interval = GameLoop::interval_from_tps(tps).unwrap();
+        assert_eq!(GameLoop::tps_from_interval(interval).unwrap(), tps);
+    }
+}
+
+#[test]
+fn tps_from_slow_interval_is_at_least_one() {
+    for secs in [1, 2, 3, 10, 60 * 60] {
+        let tps = GameLoop::tps_from_interval(Duration::from_secs(secs)).unwrap();
+        assert_eq!(tps, 1);
+    }
+    assert!(GameLoop::tps_from_interval(Duration::from_nanos(0)).is_err());
+}
+
+#[test]
+fn snapshot_restores_exact_tps() {
+    let clock = ManualClock::new();
+    let restore = |config| GameLoopBuilder::with_clock(&clock).config(config).build();
+
+    // 70_001 and 70_002 tps share the same whole-nanosecond tick duration
+    let game_loop = GameLoop::with_clock(70_001, 5, &clock).unwrap();
+    let restored = restore(game_loop.snapshot()).unwrap();
+    assert_eq!(restored.tps(), 70_001);
+    assert_eq!(restored.skip_ticks(), game_loop.skip_ticks());
+
+    // a loop created from a tick duration keeps that duration exactly
+    let tick = Duration::from_nanos(41_666_667);
+    let game_loop = GameLoopBuilder::with_clock(&clock)
+        .tick_duration(tick)
+        .build()
+        .unwrap();
+    let restored = restore(game_loop.snapshot()).unwrap();
+    assert_eq!(restored.tps(), 24);
+    assert_eq!(restored.skip_ticks(), tick);
+
+    // a restored tps is still validated
+    let mut config = game_loop.snapshot();
+    config.tps = 0;
+    assert!(matches!(restore(config), Err(GameLoopError::BadTps(0))));
+}
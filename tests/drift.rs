@@ -0,0 +1,88 @@
+//! Long-running checks that the tick rate holds over simulated hours of play.
+
+use gameloop::*;
+use std::time::Duration;
+
+const HOUR_OF_10MS_FRAMES: u32 = 360_000;
+
+fn is_tick(action: &FrameAction) -> bool {
+    matches!(action, FrameAction::Tick { .. })
+}
+
+/// Runs the loop through an hour of 10ms frames, returning the number of ticks.
+fn ticks_in_an_hour(tps: usize) -> u64 {
+    let clock = ManualClock::new();
+    let game_loop = GameLoop::with_clock(tps, 5, &clock).unwrap();
+    for _ in 0..HOUR_OF_10MS_FRAMES {
+        clock.advance(Duration::from_millis(10));
+        game_loop.actions().for_each(drop);
+    }
+    game_loop.total_ticks()
+}
+
+#[test]
+fn no_drift_over_an_hour() {
+    for tps in [50, 60, 144] {
+        let expected = tps as i64 * 60 * 60;
+        let ticks = ticks_in_an_hour(tps) as i64;
+        assert!(
+            (ticks - expected).abs() <= 1,
+            "{} tps: {} ticks",
+            tps,
+            ticks
+        );
+    }
+}
+
+#[test]
+fn film_rate_tick_duration_tracks_24_fps_over_an_hour() {
+    let clock = ManualClock::new();
+    let game_loop = GameLoopBuilder::with_clock(&clock)
+        .tick_duration(Duration::from_nanos(41_666_667))
+        .build()
+        .unwrap();
+
+    for _ in 0..HOUR_OF_10MS_FRAMES {
+        clock.advance(Duration::from_millis(10));
+        game_loop.actions().for_each(drop);
+    }
+    assert!((game_loop.total_ticks() as i64 - 24 * 60 * 60).abs() <= 1);
+}
+
+#[test]
+fn accumulator_loop_keeps_up_with_game_loop_over_an_hour() {
+    let clock = ManualClock::new();
+    let accumulator_loop = AccumulatorLoop::with_clock(60, 5, &clock).unwrap();
+    let game_loop = GameLoop::with_clock(60, 5, &clock).unwrap();
+
+    let mut accumulator_ticks = 0;
+    let mut game_loop_ticks = 0;
+    for _ in 0..HOUR_OF_10MS_FRAMES {
+        clock.advance(Duration::from_millis(10));
+        accumulator_ticks += accumulator_loop.actions().filter(is_tick).count();
+        game_loop_ticks += game_loop.actions().filter(is_tick).count();
+    }
+
+    // both keep to 60 ticks per second, without drifting
+    assert!((accumulator_ticks as i64 - 216_000).abs() <= 1);
+    assert!((accumulator_ticks as i64 - game_loop_ticks as i64).abs() <= 1);
+}
+
+#[test]
+fn ticks_at_60_tps_follow_the_exact_interval() {
+    let clock = ManualClock::new();
+    let game_loop = GameLoop::with_clock(60, 5, &clock).unwrap();
+    assert_eq!(game_loop.skip_ticks(), Duration::from_nanos(16_666_666));
+
+    // 10 seconds of 10ms frames
+    let elapsed = Duration::from_secs(10);
+    for _ in 0..1000 {
+        clock.advance(Duration::from_millis(10));
+        game_loop.actions().for_each(drop);
+    }
+
+    // one tick is due immediately, then one every 16.666ms. A 16ms interval would give 626
+    let expected = 1 + elapsed.as_nanos() / game_loop.skip_ticks().as_nanos();
+    assert_eq!(expected, 601);
+    assert_eq!(game_loop.total_ticks() as u128, expected);
+}
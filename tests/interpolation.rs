@@ -0,0 +1,53 @@
+//! Checks of which ticks a render interpolates between.
+
+use gameloop::*;
+use std::time::Duration;
+
+#[test]
+fn renders_sit_between_the_previous_and_latest_ticks() {
+    let clock = ManualClock::new();
+    let game_loop = GameLoopBuilder::with_clock(&clock)
+        .tps(20)
+        .max_frameskip(5)
+        .clamp_interpolation(true)
+        .build()
+        .unwrap();
+    let tick = game_loop.skip_ticks();
+
+    // the game state is the number of the tick that produced it
+    let mut previous = 0;
+    let mut latest = 0;
+    let mut renders = 0;
+
+    for frame_time in [7, 16, 33, 5, 12, 41, 3, 22].iter().cycle().take(400) {
+        clock.advance(Duration::from_millis(*frame_time));
+        for action in game_loop.actions() {
+            match action {
+                FrameAction::Tick { .. } => {
+                    previous = latest;
+                    latest = game_loop.total_ticks();
+                }
+                // before the second tick, there is no pair to interpolate between
+                FrameAction::Render { .. } if latest < 2 => {}
+                FrameAction::Render { interpolation } => {
+                    renders += 1;
+
+                    // the render is between tick N-1 and tick N, both of which have run
+                    assert_eq!(previous + 1, latest);
+                    assert!((0.0..=1.0).contains(&interpolation));
+
+                    // tick N ran at (N - 1) ticks of game time, so the rendered state is exactly
+                    // one tick behind the clock
+                    let rendered_time =
+                        tick.mul_f64((previous - 1) as f64) + tick.mul_f64(interpolation);
+                    let lag = clock.now().as_secs_f64() - rendered_time.as_secs_f64();
+                    assert!((lag - tick.as_secs_f64()).abs() < 1e-6, "lag {}", lag);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // all but the first two frames, which are before the second tick at 50ms
+    assert_eq!(renders, 398);
+}
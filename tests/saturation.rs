@@ -0,0 +1,33 @@
+//! Checks that durations near the limit saturate rather than wrap.
+
+use gameloop::*;
+use std::time::Duration;
+
+#[test]
+fn manual_clock_saturates_rather_than_wrapping() {
+    let clock = ManualClock::new();
+    clock.advance(Duration::from_secs(1));
+    let before = clock.now();
+
+    clock.advance(Duration::MAX);
+    let after = clock.now();
+    assert!(after > before);
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(clock.now(), after);
+}
+
+#[test]
+fn skipping_all_time_never_moves_the_next_tick_back() {
+    let clock = ManualClock::new();
+    let game_loop = GameLoop::with_clock(20, 5, &clock).unwrap();
+    game_loop.actions().for_each(drop);
+    clock.advance(Duration::from_millis(100));
+    game_loop.actions().for_each(drop);
+
+    game_loop.skip_time(Duration::MAX);
+    assert_eq!(game_loop.pending_ticks(), 0);
+
+    clock.advance(Duration::from_secs(60 * 60));
+    assert_eq!(game_loop.pending_ticks(), 0);
+}
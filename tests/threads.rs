@@ -0,0 +1,43 @@
+//! Checks of a loop shared between threads.
+
+use gameloop::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[test]
+fn render_thread_polls_interpolation_while_ticks_are_claimed() {
+    let clock = ManualClock::new();
+    let game_loop = GameLoop::with_clock(20, 100, &clock).unwrap();
+    let done = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        let render = scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                let interpolation = game_loop.current_interpolation();
+                assert!(interpolation >= 0.0);
+            }
+        });
+
+        // each frame claims many ticks, moving the next tick well past a stale render time
+        for _ in 0..50_000 {
+            clock.advance(Duration::from_millis(500));
+            game_loop.actions().for_each(drop);
+        }
+
+        done.store(true, Ordering::Relaxed);
+        render.join().unwrap();
+    });
+}
+
+#[test]
+fn interpolation_of_a_render_time_older_than_the_last_tick() {
+    let clock = ManualClock::new();
+    let game_loop = GameLoop::with_clock(20, 100, &clock).unwrap();
+
+    clock.advance(Duration::from_secs(1));
+    game_loop.actions().for_each(drop);
+
+    // the same ordering as a render thread that read the time before the ticks were claimed
+    clock.rewind(Duration::from_secs(1));
+    assert_eq!(game_loop.current_interpolation(), 0.0);
+}